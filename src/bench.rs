@@ -0,0 +1,357 @@
+use std::{
+  fmt, fs, hint, io,
+  path::Path,
+  time::{Duration, Instant},
+};
+
+use crate::{Day, ParseError, Part, json::Value, params::Params};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+  /// How long to run the solution before measuring anything.
+  pub warmup: Duration,
+  /// How many samples to take, unless `max_time` runs out first.
+  pub samples: usize,
+  pub max_time: Duration,
+}
+
+impl Default for Config {
+  fn default() -> Self {
+    Self {
+      warmup: Duration::from_millis(200),
+      samples: 100,
+      max_time: Duration::from_secs(5),
+    }
+  }
+}
+
+/// Order statistics over a set of timings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+  pub samples: usize,
+  pub min: Duration,
+  pub median: Duration,
+  pub p95: Duration,
+}
+
+impl Summary {
+  pub fn new(mut samples: Vec<Duration>) -> Self {
+    assert!(!samples.is_empty(), "no samples");
+    samples.sort_unstable();
+    let percentile = |p: usize| samples[(samples.len() * p).div_ceil(100) - 1];
+    Self {
+      samples: samples.len(),
+      min: samples[0],
+      median: percentile(50),
+      p95: percentile(95),
+    }
+  }
+
+  fn to_json(self) -> Value {
+    let nanos = |duration: Duration| {
+      Value::Int(i128::try_from(duration.as_nanos()).unwrap_or(i128::MAX))
+    };
+    Value::Object(vec![
+      ("samples".into(), self.samples.into()),
+      ("min_ns".into(), nanos(self.min)),
+      ("median_ns".into(), nanos(self.median)),
+      ("p95_ns".into(), nanos(self.p95)),
+    ])
+  }
+
+  fn from_json(value: &Value) -> Option<Self> {
+    let nanos = |key| {
+      let nanos = value.get(key)?.as_int()?;
+      Some(Duration::from_nanos(u64::try_from(nanos).ok()?))
+    };
+    Some(Self {
+      samples: usize::try_from(value.get("samples")?.as_int()?).ok()?,
+      min: nanos("min_ns")?,
+      median: nanos("median_ns")?,
+      p95: nanos("p95_ns")?,
+    })
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measurement {
+  pub day: u8,
+  pub part: Part,
+  pub parse: Summary,
+  pub solve: Summary,
+}
+
+pub const HEADER: &str = "\
+day   part  parse min     median        p95  solve min     median        p95  samples";
+
+impl fmt::Display for Measurement {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{:02}    {:<4}", self.day, self.part)?;
+    for summary in [self.parse, self.solve] {
+      for duration in [summary.min, summary.median, summary.p95] {
+        write!(f, " {:>10}", format_duration(duration))?;
+      }
+    }
+    write!(f, " {:>8}", self.solve.samples)
+  }
+}
+
+fn sample(config: &Config, mut f: impl FnMut()) -> Summary {
+  let start = Instant::now();
+  while start.elapsed() < config.warmup {
+    f();
+  }
+
+  let start = Instant::now();
+  let mut samples = Vec::with_capacity(config.samples);
+  while samples.len() < config.samples.max(1)
+    && (samples.is_empty() || start.elapsed() < config.max_time)
+  {
+    let sample_start = Instant::now();
+    f();
+    samples.push(sample_start.elapsed());
+  }
+
+  Summary::new(samples)
+}
+
+/// Times parsing and solving separately, returning `None` if the day has no
+/// such part.
+pub fn measure(
+  day: &Day,
+  input: &str,
+  part: Part,
+  params: &Params,
+  config: &Config,
+) -> Result<Option<Measurement>, ParseError> {
+  let parsed = day.parse(input, params)?;
+  if parsed.solve(part).is_none() {
+    return Ok(None);
+  }
+
+  let parse = sample(config, || {
+    hint::black_box(day.parse(hint::black_box(input), params).ok());
+  });
+
+  let solve = sample(config, || {
+    hint::black_box(hint::black_box(&parsed).solve(part));
+  });
+
+  Ok(Some(Measurement {
+    day: day.number,
+    part,
+    parse,
+    solve,
+  }))
+}
+
+/// A saved set of measurements to compare later runs against.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline {
+  pub measurements: Vec<Measurement>,
+}
+
+impl Baseline {
+  pub fn load(path: &Path) -> io::Result<Self> {
+    let invalid = |message: String| {
+      io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {message}", path.display()),
+      )
+    };
+
+    let value = Value::parse(&fs::read_to_string(path)?).map_err(invalid)?;
+
+    let measurements = value
+      .get("measurements")
+      .and_then(Value::as_array)
+      .ok_or_else(|| invalid("expected a measurements array".into()))?
+      .iter()
+      .map(|value| {
+        Some(Measurement {
+          day: u8::try_from(value.get("day")?.as_int()?).ok()?,
+          part: u8::try_from(value.get("part")?.as_int()?)
+            .ok()?
+            .try_into()
+            .ok()?,
+          parse: Summary::from_json(value.get("parse")?)?,
+          solve: Summary::from_json(value.get("solve")?)?,
+        })
+      })
+      .collect::<Option<_>>()
+      .ok_or_else(|| invalid("malformed measurement".into()))?;
+
+    Ok(Self { measurements })
+  }
+
+  pub fn save(&self, path: &Path) -> io::Result<()> {
+    let measurements = self
+      .measurements
+      .iter()
+      .map(|measurement| {
+        Value::Object(vec![
+          ("day".into(), measurement.day.into()),
+          ("part".into(), measurement.part.number().into()),
+          ("parse".into(), measurement.parse.to_json()),
+          ("solve".into(), measurement.solve.to_json()),
+        ])
+      })
+      .collect();
+
+    let value =
+      Value::Object(vec![("measurements".into(), Value::Array(measurements))]);
+
+    fs::write(path, format!("{value:#}\n"))
+  }
+
+  pub fn get(&self, day: u8, part: Part) -> Option<&Measurement> {
+    self
+      .measurements
+      .iter()
+      .find(|measurement| measurement.day == day && measurement.part == part)
+  }
+}
+
+/// A measurement whose median got slower than the baseline allows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Regression {
+  pub day: u8,
+  pub part: Part,
+  pub phase: &'static str,
+  pub baseline: Duration,
+  pub current: Duration,
+}
+
+impl Regression {
+  pub fn ratio(&self) -> f64 {
+    self.current.as_secs_f64() / self.baseline.as_secs_f64()
+  }
+}
+
+impl fmt::Display for Regression {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "day{:02} part{} {}: {} -> {} (+{:.1}%)",
+      self.day,
+      self.part,
+      self.phase,
+      format_duration(self.baseline),
+      format_duration(self.current),
+      (self.ratio() - 1.0) * 100.0
+    )
+  }
+}
+
+/// Compares medians, flagging any that grew by more than `threshold`
+/// (e.g. 0.1 for 10%).
+pub fn compare(
+  baseline: &Baseline,
+  current: &[Measurement],
+  threshold: f64,
+) -> Vec<Regression> {
+  let mut regressions = Vec::new();
+
+  for measurement in current {
+    let Some(previous) = baseline.get(measurement.day, measurement.part) else {
+      continue;
+    };
+
+    for (phase, baseline, current) in [
+      ("parse", previous.parse.median, measurement.parse.median),
+      ("solve", previous.solve.median, measurement.solve.median),
+    ] {
+      let regression = Regression {
+        day: measurement.day,
+        part: measurement.part,
+        phase,
+        baseline,
+        current,
+      };
+      if regression.ratio() > 1.0 + threshold {
+        regressions.push(regression);
+      }
+    }
+  }
+
+  regressions
+}
+
+pub fn format_duration(duration: Duration) -> String {
+  let micros = duration.as_secs_f64() * 1e6;
+  if micros < 1e3 {
+    format!("{micros:.1}µs")
+  } else if micros < 1e6 {
+    format!("{:.2}ms", micros / 1e3)
+  } else {
+    format!("{:.2}s", micros / 1e6)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn summary(median_ms: u64) -> Summary {
+    let median = Duration::from_millis(median_ms);
+    Summary {
+      samples: 10,
+      min: median / 2,
+      median,
+      p95: median * 2,
+    }
+  }
+
+  #[test]
+  fn summarizes_samples() {
+    let summary =
+      Summary::new((1..=100).rev().map(Duration::from_micros).collect());
+    assert_eq!(summary.samples, 100);
+    assert_eq!(summary.min, Duration::from_micros(1));
+    assert_eq!(summary.median, Duration::from_micros(50));
+    assert_eq!(summary.p95, Duration::from_micros(95));
+  }
+
+  #[test]
+  fn flags_regressions() {
+    let baseline = Baseline {
+      measurements: vec![Measurement {
+        day: 8,
+        part: Part::Two,
+        parse: summary(10),
+        solve: summary(100),
+      }],
+    };
+
+    let current = [Measurement {
+      day: 8,
+      part: Part::Two,
+      parse: summary(10),
+      solve: summary(130),
+    }];
+
+    let regressions = compare(&baseline, &current, 0.1);
+    assert_eq!(regressions.len(), 1);
+    assert_eq!(regressions[0].phase, "solve");
+    assert!(compare(&baseline, &current, 0.5).is_empty());
+  }
+
+  #[test]
+  fn round_trips_baseline() {
+    let baseline = Baseline {
+      measurements: vec![Measurement {
+        day: 1,
+        part: Part::One,
+        parse: summary(1),
+        solve: summary(2),
+      }],
+    };
+
+    let path = std::env::temp_dir()
+      .join(format!("aoc-baseline-{}.json", std::process::id()));
+    baseline.save(&path).unwrap();
+    let loaded = Baseline::load(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.unwrap(), baseline);
+  }
+}
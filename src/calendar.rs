@@ -0,0 +1,595 @@
+use std::{
+  fmt::{self, Write},
+  fs,
+  path::{Path, PathBuf},
+  process::Command,
+  str::{self, FromStr},
+  sync::atomic::{AtomicUsize, Ordering},
+  thread,
+  time::{Duration, Instant},
+};
+
+use crate::{
+  Answer, Day, Part,
+  bench::format_duration,
+  input::{self, Inputs},
+  json::Value,
+  manifest::Manifest,
+  params::Params,
+  sandbox::{self, Failure, Limits},
+};
+
+/// How an answer compares with the one in the manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+  Passed,
+  Failed,
+  /// The manifest has no answer to compare with.
+  Unchecked,
+}
+
+impl fmt::Display for Status {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.pad(match self {
+      Status::Passed => "pass",
+      Status::Failed => "FAIL",
+      Status::Unchecked => "-",
+    })
+  }
+}
+
+impl Status {
+  fn name(self) -> &'static str {
+    match self {
+      Status::Passed => "passed",
+      Status::Failed => "failed",
+      Status::Unchecked => "unchecked",
+    }
+  }
+}
+
+/// How to print a [`Calendar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+  Text,
+  Json,
+  Csv,
+}
+
+impl FromStr for Format {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "text" => Ok(Format::Text),
+      "json" => Ok(Format::Json),
+      "csv" => Ok(Format::Csv),
+      _ => Err(format!("unknown format: {s}")),
+    }
+  }
+}
+
+/// One part of one day, solved against its input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+  pub day: u8,
+  pub part: Part,
+  /// The answer, or why there is none.
+  pub answer: Result<Answer, String>,
+  pub expected: Option<String>,
+  pub parse: Duration,
+  pub solve: Duration,
+}
+
+impl Row {
+  pub fn new(
+    day: &Day,
+    part: Part,
+    solved: Solved,
+    expected: Option<&str>,
+  ) -> Self {
+    Self {
+      day: day.number,
+      part,
+      answer: solved.answer,
+      expected: expected.map(str::to_owned),
+      parse: solved.parse,
+      solve: solved.solve,
+    }
+  }
+
+  pub fn status(&self) -> Status {
+    match (&self.answer, &self.expected) {
+      (Ok(_), None) => Status::Unchecked,
+      (Ok(answer), Some(expected)) if answer.to_string() == *expected => {
+        Status::Passed
+      }
+      _ => Status::Failed,
+    }
+  }
+
+  fn to_json(&self) -> Value {
+    let answer = self.answer.as_ref().ok();
+    Value::Object(vec![
+      ("day".into(), self.day.into()),
+      ("part".into(), self.part.number().into()),
+      ("answer".into(), answer.map_or(Value::Null, answer_value)),
+      (
+        "answer_type".into(),
+        answer.map(|answer| answer.type_name()).into(),
+      ),
+      ("expected".into(), self.expected.clone().into()),
+      ("error".into(), self.answer.as_ref().err().cloned().into()),
+      ("parse_ns".into(), nanos(self.parse).into()),
+      ("solve_ns".into(), nanos(self.solve).into()),
+      ("status".into(), self.status().name().into()),
+    ])
+  }
+
+  fn write_csv(&self, out: &mut String) {
+    let answer = self.answer.as_ref().ok();
+    let fields = [
+      self.day.to_string(),
+      self.part.to_string(),
+      answer.map(ToString::to_string).unwrap_or_default(),
+      answer
+        .map(|answer| answer.type_name().into())
+        .unwrap_or_default(),
+      self.expected.clone().unwrap_or_default(),
+      self.answer.clone().err().unwrap_or_default(),
+      nanos(self.parse).to_string(),
+      nanos(self.solve).to_string(),
+      self.status().name().into(),
+    ];
+    let fields: Vec<_> = fields.iter().map(|field| csv_field(field)).collect();
+    out.push_str(&fields.join(","));
+    out.push('\n');
+  }
+}
+
+/// Answers of types that can pass 2^53 are written as strings, since many
+/// JSON readers keep every number in a double and would round them.
+fn answer_value(&answer: &Answer) -> Value {
+  match answer {
+    Answer::U16(value) => value.into(),
+    Answer::U64(_) | Answer::U128(_) | Answer::Usize(_) => {
+      answer.to_string().into()
+    }
+  }
+}
+
+fn nanos(duration: Duration) -> u64 {
+  u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// Quotes a CSV field if it needs it, doubling any quotes inside it.
+pub(crate) fn csv_field(field: &str) -> String {
+  if field.contains([',', '"', '\n']) {
+    format!("\"{}\"", field.replace('"', "\"\""))
+  } else {
+    field.into()
+  }
+}
+
+pub const HEADER: &str = "\
+day   part  answer           expected         status      parse      solve";
+
+const CSV_HEADER: &str =
+  "day,part,answer,answer_type,expected,error,parse_ns,solve_ns,status";
+
+impl fmt::Display for Row {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let answer = match &self.answer {
+      Ok(answer) => answer.to_string(),
+      Err(_) => "error".into(),
+    };
+    write!(
+      f,
+      "{:02}    {:<4}  {:<16} {:<16} {:<6} {:>10} {:>10}",
+      self.day,
+      self.part,
+      answer,
+      self.expected.as_deref().unwrap_or("-"),
+      self.status(),
+      format_duration(self.parse),
+      format_duration(self.solve),
+    )
+  }
+}
+
+/// Every row of a run, in day and part order however it was scheduled.
+#[derive(Debug, Clone)]
+pub struct Calendar {
+  pub rows: Vec<Row>,
+  /// Days whose input could not be loaded, and why.
+  pub skipped: Vec<(u8, String)>,
+  /// Wall-clock time for the whole run.
+  pub elapsed: Duration,
+}
+
+impl Calendar {
+  /// The time spent parsing and solving, summed over every row.
+  pub fn total(&self) -> Duration {
+    self.rows.iter().map(|row| row.parse + row.solve).sum()
+  }
+
+  pub fn failures(&self) -> usize {
+    self
+      .rows
+      .iter()
+      .filter(|row| row.status() == Status::Failed)
+      .count()
+  }
+
+  /// The rows as a table with a closing total, a JSON array of records, or
+  /// CSV with a header line.
+  pub fn render(&self, format: Format) -> String {
+    match format {
+      Format::Text => {
+        let mut out = format!("{HEADER}\n");
+        for row in &self.rows {
+          writeln!(out, "{row}").unwrap();
+        }
+        writeln!(
+          out,
+          "total {} ({} wall clock)",
+          format_duration(self.total()),
+          format_duration(self.elapsed)
+        )
+        .unwrap();
+        out
+      }
+      Format::Json => {
+        let rows = self.rows.iter().map(Row::to_json).collect();
+        format!("{:#}\n", Value::Array(rows))
+      }
+      Format::Csv => {
+        let mut out = format!("{CSV_HEADER}\n");
+        for row in &self.rows {
+          row.write_csv(&mut out);
+        }
+        out
+      }
+    }
+  }
+}
+
+/// What solving one part produced, before comparing it with the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solved {
+  /// The answer, or why there is none.
+  pub answer: Result<Answer, String>,
+  pub parse: Duration,
+  pub solve: Duration,
+}
+
+impl Solved {
+  fn failed(failure: &Failure) -> Self {
+    Self {
+      answer: Err(failure.to_string()),
+      parse: Duration::ZERO,
+      solve: Duration::ZERO,
+    }
+  }
+
+  fn from_json(record: &Value) -> Option<Self> {
+    let nanos = |key| {
+      let nanos = record.get(key)?.as_int()?;
+      Some(Duration::from_nanos(u64::try_from(nanos).ok()?))
+    };
+    let answer = match record.get("error").and_then(Value::as_str) {
+      Some(error) => Err(error.to_owned()),
+      None => Ok(answer_from(
+        record.get("answer_type")?.as_str()?,
+        record.get("answer")?,
+      )?),
+    };
+    Some(Self {
+      answer,
+      parse: nanos("parse_ns")?,
+      solve: nanos("solve_ns")?,
+    })
+  }
+}
+
+fn answer_from(type_name: &str, value: &Value) -> Option<Answer> {
+  let value = match value {
+    Value::Int(value) => value.to_string(),
+    value => value.as_str()?.to_owned(),
+  };
+  match type_name {
+    "u16" => value.parse().ok().map(Answer::U16),
+    "u64" => value.parse().ok().map(Answer::U64),
+    "u128" => value.parse().ok().map(Answer::U128),
+    "usize" => value.parse().ok().map(Answer::Usize),
+    _ => None,
+  }
+}
+
+/// Parses an input and solves one part of it on a thread of its own, or
+/// returns `None` if the day has no such part. Each part parses the input
+/// afresh, so that a panic in one cannot take the other with it.
+pub fn solve(
+  day: &Day,
+  input: &str,
+  part: Part,
+  params: &Params,
+  limits: Limits,
+) -> Option<Solved> {
+  let day = *day;
+  let input = input.to_owned();
+  let params = params.clone();
+  let name = format!("day{:02} part{part}", day.number);
+
+  let result = sandbox::run(name, limits, move || {
+    let start = Instant::now();
+    let parsed = day.parse(&input, &params);
+    let parse = start.elapsed();
+
+    let parsed = match parsed {
+      Ok(parsed) => parsed,
+      Err(err) => {
+        return Some(Solved {
+          answer: Err(err.to_string()),
+          parse,
+          solve: Duration::ZERO,
+        });
+      }
+    };
+
+    let start = Instant::now();
+    let answer = parsed.solve(part)?;
+    Some(Solved {
+      answer: Ok(answer),
+      parse,
+      solve: start.elapsed(),
+    })
+  });
+
+  result.unwrap_or_else(|failure| Some(Solved::failed(&failure)))
+}
+
+/// Like [`solve`], but in a child process running `aoc run` from `exe`,
+/// which also survives the solver overflowing its stack.
+fn solve_in_process(
+  exe: &Path,
+  day: &Day,
+  path: &Path,
+  part: Part,
+  params: &Params,
+  limits: Limits,
+) -> Option<Solved> {
+  let mut command = Command::new(exe);
+  command
+    .args(["run", &day.number.to_string(), &part.to_string()])
+    .arg("--input")
+    .arg(path)
+    .args(["--format", "json"])
+    .args(["--deadline", &limits.deadline.as_millis().to_string()])
+    .args(["--stack", &(limits.stack_size >> 20).max(1).to_string()]);
+  for (name, value) in params.iter() {
+    command.args(["--param", &format!("{name}={value}")]);
+  }
+
+  // The child enforces the deadline itself, and reports it like any other
+  // failure, so only step in if it gets stuck on the way.
+  let output =
+    match sandbox::run_process(&mut command, limits.deadline + PROCESS_GRACE) {
+      Ok(output) => output,
+      Err(failure) => return Some(Solved::failed(&failure)),
+    };
+
+  let records = str::from_utf8(&output.stdout)
+    .ok()
+    .and_then(|stdout| Value::parse(stdout).ok());
+  if let Some(records) = records.as_ref().and_then(Value::as_array) {
+    return records.first().map(|record| {
+      Solved::from_json(record).unwrap_or_else(|| {
+        Solved::failed(&Failure::Crashed("unreadable result".into()))
+      })
+    });
+  }
+
+  let stderr = String::from_utf8_lossy(&output.stderr);
+  let reason = stderr.lines().rfind(|line| !line.is_empty()).unwrap_or("");
+  Some(Solved::failed(&Failure::Crashed(format!(
+    "{}: {reason}",
+    output.status
+  ))))
+}
+
+/// Time for a child process to start up and report back after its deadline.
+const PROCESS_GRACE: Duration = Duration::from_secs(1);
+
+/// Where each part is solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Isolation {
+  /// On a thread of its own, which catches panics but not stack overflows.
+  /// A part that misses its deadline is abandoned rather than stopped, so it
+  /// keeps its thread busy until the process exits.
+  Thread,
+  /// In a child process running `aoc run` from the given binary.
+  Process(PathBuf),
+}
+
+#[derive(Debug, Clone)]
+pub struct Options {
+  /// How many days to solve at once.
+  pub jobs: usize,
+  pub limits: Limits,
+  pub isolation: Isolation,
+}
+
+impl Default for Options {
+  fn default() -> Self {
+    Self {
+      jobs: 1,
+      limits: Limits::default(),
+      isolation: Isolation::Thread,
+    }
+  }
+}
+
+fn run_day(
+  day: &Day,
+  inputs: &Inputs,
+  name: &str,
+  manifest: &Manifest,
+  options: &Options,
+) -> Result<Vec<Row>, String> {
+  let path = inputs.resolve(day.number, name).ok_or_else(|| {
+    format!(
+      "no input {name:?} at {}",
+      inputs.path(day.number, name).display()
+    )
+  })?;
+  let input = fs::read_to_string(&path)
+    .map(|input| input::normalize(&input))
+    .map_err(|err| format!("{}: {err}", path.display()))?;
+  let params = day.resolve(manifest.params(day.number, name))?;
+
+  let mut rows = Vec::new();
+  for &part in day.parts {
+    let solved = match &options.isolation {
+      Isolation::Thread => solve(day, &input, part, &params, options.limits),
+      Isolation::Process(exe) => {
+        solve_in_process(exe, day, &path, part, &params, options.limits)
+      }
+    };
+    if let Some(solved) = solved {
+      let expected = manifest.expected(day.number, name, part);
+      rows.push(Row::new(day, part, solved, expected));
+    }
+  }
+
+  Ok(rows)
+}
+
+/// Solves the input called `name` for every day, spreading the days over
+/// `options.jobs` threads.
+pub fn run(
+  days: &[&Day],
+  inputs: &Inputs,
+  name: &str,
+  manifest: &Manifest,
+  options: &Options,
+) -> Calendar {
+  let start = Instant::now();
+  let next = AtomicUsize::new(0);
+
+  let mut results = thread::scope(|scope| {
+    let workers: Vec<_> = (0..options.jobs.clamp(1, days.len().max(1)))
+      .map(|_| {
+        scope.spawn(|| {
+          let mut results = Vec::new();
+          loop {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let Some(day) = days.get(index) else {
+              break results;
+            };
+            results
+              .push((index, run_day(day, inputs, name, manifest, options)));
+          }
+        })
+      })
+      .collect();
+
+    workers
+      .into_iter()
+      .flat_map(|worker| worker.join().unwrap())
+      .collect::<Vec<_>>()
+  });
+  results.sort_by_key(|(index, _)| *index);
+
+  let mut rows = Vec::new();
+  let mut skipped = Vec::new();
+  for (index, result) in results {
+    match result {
+      Ok(day_rows) => rows.extend(day_rows),
+      Err(err) => skipped.push((days[index].number, err)),
+    }
+  }
+
+  Calendar {
+    rows,
+    skipped,
+    elapsed: start.elapsed(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::DAYS;
+
+  #[test]
+  fn runs_in_order() {
+    let days: Vec<&Day> = DAYS.iter().collect();
+    let inputs = Inputs::new(std::env::temp_dir().join("aoc-no-inputs"));
+    let manifest = Manifest::load(&Manifest::default_path()).unwrap();
+
+    let key = |calendar: &Calendar| -> Vec<_> {
+      calendar
+        .rows
+        .iter()
+        .map(|row| (row.day, row.part, row.answer.clone(), row.status()))
+        .collect()
+    };
+
+    let parallel = Options {
+      jobs: 4,
+      ..Options::default()
+    };
+    let serial =
+      run(&days, &inputs, "input_test", &manifest, &Options::default());
+    let parallel = run(&days, &inputs, "input_test", &manifest, &parallel);
+    assert_eq!(key(&serial), key(&parallel));
+    assert_eq!(serial.skipped, parallel.skipped);
+
+    assert!(serial.rows.is_sorted_by_key(|row| (row.day, row.part)));
+    assert_eq!(serial.failures(), 0);
+    assert!(serial.rows.iter().any(|row| row.status() == Status::Passed));
+
+    let json = Value::parse(&serial.render(Format::Json)).unwrap();
+    let records = json.as_array().unwrap();
+    assert_eq!(records.len(), serial.rows.len());
+    assert_eq!(records[0].get("day").and_then(Value::as_int), Some(1));
+    assert_eq!(
+      Solved::from_json(&records[0]).map(|solved| solved.answer),
+      Some(serial.rows[0].answer.clone())
+    );
+    assert_eq!(
+      records[0].get("status").and_then(Value::as_str),
+      Some("passed")
+    );
+
+    let csv = serial.render(Format::Csv);
+    assert_eq!(csv.lines().count(), serial.rows.len() + 1);
+    assert_eq!(csv_field("a \"b\", c"), "\"a \"\"b\"\", c\"");
+  }
+
+  #[test]
+  fn keeps_large_answers_exact() {
+    let day = crate::day(1).unwrap();
+    for answer in [
+      Answer::U16(7),
+      Answer::U64(u64::MAX),
+      Answer::U128(u128::MAX),
+      Answer::Usize((1 << 53) + 1),
+    ] {
+      let solved = Solved {
+        answer: Ok(answer),
+        parse: Duration::ZERO,
+        solve: Duration::ZERO,
+      };
+      let record = Row::new(day, Part::One, solved, None).to_json();
+      let json = Value::parse(&record.to_string()).unwrap();
+      let value = json.get("answer").unwrap();
+      assert_eq!(value.as_str().is_some(), answer.type_name() != "u16");
+      assert_eq!(
+        Solved::from_json(&json).map(|solved| solved.answer),
+        Some(Ok(answer))
+      );
+    }
+  }
+}
@@ -1,27 +1,40 @@
-pub const fn part1(input: &str) -> usize {
+use crate::{Answer, ParseError, Solution, params::Params, rng::Rng};
+
+const fn part1(input: &str) -> usize {
   input.len()
 }
 
-pub const fn part2(input: &str) -> usize {
+const fn part2(input: &str) -> usize {
   input.len()
 }
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+fn generate(_rng: &mut Rng, _size: usize) -> String {
+  String::new()
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+  const DAY: u8 = 0;
 
-  const INPUT_TEST: &str = include_str!("input_test");
-  const INPUT: &str = include_str!("input");
+  type Input<'a> = &'a str;
+
+  fn parse<'a>(
+    input: &'a str,
+    _params: &Params,
+  ) -> Result<Self::Input<'a>, ParseError> {
+    Ok(input)
+  }
+
+  fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+    generate(rng, size)
+  }
 
-  #[test]
-  fn part1_works() {
-    assert_eq!(part1(INPUT_TEST), 0);
-    assert_eq!(part1(INPUT), 0);
+  fn part1(input: &Self::Input<'_>) -> Answer {
+    part1(input).into()
   }
 
-  #[test]
-  fn part2_works() {
-    assert_eq!(part2(INPUT_TEST), 0);
-    assert_eq!(part2(INPUT), 0);
+  fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+    Some(part2(input).into())
   }
 }
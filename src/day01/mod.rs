@@ -1,55 +1,273 @@
-fn parse_input(input: &str) -> impl Iterator<Item = isize> {
-  input.lines().map(|line| {
-    let (dir, len) = line.split_at(1);
-    let len: isize = len.parse().unwrap();
-    match dir {
-      "L" => -len,
-      "R" => len,
-      _ => panic!("invalid direction"),
-    }
-  })
+mod reference;
+
+use std::fmt::Write;
+
+use crate::{
+  Answer, ParseError, Part, Solution,
+  params::{Param, Params},
+  parse::Cursor,
+  rng::Rng,
+  trace::Trace,
+};
+
+pub struct Rotations {
+  /// Where the dial points before the first rotation.
+  start: isize,
+  steps: Box<[isize]>,
+}
+
+fn parse_input(input: &str, start: isize) -> Result<Rotations, ParseError> {
+  let steps = Cursor::new(Puzzle::DAY, input)
+    .lines()
+    .map(|mut line| {
+      let sign = match line.peek() {
+        Some('L') => -1,
+        Some('R') => 1,
+        _ => return Err(line.error("'L' or 'R'")),
+      };
+      line.bump();
+      let len: isize = line.number()?;
+      line.end()?;
+      Ok(sign * len)
+    })
+    .collect::<Result<_, _>>()?;
+
+  Ok(Rotations { start, steps })
+}
+
+impl Rotations {
+  /// Each rotation in clicks, positive to the right and negative to the left.
+  pub fn steps(&self) -> &[isize] {
+    &self.steps
+  }
+}
+
+/// A target that one rotation pointed the dial at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hit {
+  pub target: isize,
+  /// How many clicks pointed at the target, including the last one.
+  pub passes: usize,
+  /// Whether the rotation ended on the target.
+  pub landed: bool,
 }
 
-pub fn part1(input: &str) -> usize {
-  let mut val = 50;
-  let mut count = 0;
+/// What one rotation did to a [`Dial`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+  /// Which rotation this was, counting from 0.
+  pub index: usize,
+  pub step: isize,
+  pub start: isize,
+  pub end: isize,
+  /// Every target the rotation reached, in the dial's order.
+  pub hits: Vec<Hit>,
+}
+
+/// A dial of `size` positions, numbered from 0, that counts how often
+/// rotations point it at each of a set of targets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dial {
+  size: isize,
+  position: isize,
+  targets: Box<[isize]>,
+}
 
-  for step in parse_input(input) {
-    val += step;
-    while val < 0 {
-      val += 100;
+impl Dial {
+  /// Panics unless `size` is positive and every position lies below it.
+  /// Targets after the first with the same position are left out.
+  pub fn new(
+    size: isize,
+    start: isize,
+    targets: impl IntoIterator<Item = isize>,
+  ) -> Self {
+    assert!(size > 0, "a dial needs at least one position: {size}");
+    let mut unique = Vec::new();
+    for target in targets {
+      if !unique.contains(&target) {
+        unique.push(target);
+      }
     }
-    val %= 100;
-    if val == 0 {
-      count += 1;
+    let targets = unique.into_boxed_slice();
+    for position in targets.iter().chain([&start]) {
+      assert!(
+        (0..size).contains(position),
+        "position {position} is not on a dial of {size}"
+      );
+    }
+    Self {
+      size,
+      position: start,
+      targets,
     }
   }
 
-  count
-}
+  pub fn position(&self) -> isize {
+    self.position
+  }
+
+  pub fn targets(&self) -> &[isize] {
+    &self.targets
+  }
 
-pub fn part2(input: &str) -> usize {
-  let mut val = 50;
-  let mut count = 0;
+  /// Turns the dial by `step` clicks, to the right if positive.
+  pub fn turn(&mut self, step: isize) {
+    self.position = (self.position + step.rem_euclid(self.size)) % self.size;
+  }
 
-  for step in parse_input(input) {
-    let sig = step.signum();
-    let len = step.abs();
+  /// How many clicks of turning by `step` from `start` point the dial at
+  /// `target`, counting the last one: once per full turn, plus once more if
+  /// the rest reaches it.
+  fn passes(&self, start: isize, target: isize, step: isize) -> usize {
+    // Turning left towards the target is turning right away from it.
+    let distance = if step < 0 {
+      target - start
+    } else {
+      start - target
+    }
+    .rem_euclid(self.size);
+    let len = step.unsigned_abs();
+    let size = self.size.unsigned_abs();
+    len / size + (distance.unsigned_abs() + len % size) / size
+  }
 
-    for _ in 0..len {
-      val += sig;
-      match val {
-        -1 => val = 99,
-        100 => val = 0,
-        _ => {}
+  /// Follows `steps` from the current position, reporting what each one did.
+  pub fn trace<'s>(
+    &self,
+    steps: &'s [isize],
+  ) -> impl Iterator<Item = Event> + 's {
+    let mut dial = self.clone();
+    steps.iter().enumerate().map(move |(index, &step)| {
+      let start = dial.position;
+      dial.turn(step);
+      let end = dial.position;
+      let hits = dial
+        .targets
+        .iter()
+        .map(|&target| Hit {
+          target,
+          passes: dial.passes(start, target, step),
+          landed: target == end,
+        })
+        .filter(|hit| hit.passes > 0 || hit.landed)
+        .collect();
+      Event {
+        index,
+        step,
+        start,
+        end,
+        hits,
       }
-      if val == 0 {
-        count += 1;
+    })
+  }
+
+  /// Adds up `count` for every hit on each target, over every rotation.
+  fn count(
+    &self,
+    steps: &[isize],
+    count: impl Fn(&Hit) -> usize,
+  ) -> Box<[usize]> {
+    let mut counts = vec![0; self.targets.len()];
+    for event in self.trace(steps) {
+      for hit in &event.hits {
+        let target = self.targets.iter().position(|&t| t == hit.target);
+        counts[target.unwrap()] += count(hit);
       }
     }
+    counts.into_boxed_slice()
+  }
+
+  /// Follows `steps` from the current position, counting for each target
+  /// how many rotations end on it.
+  pub fn count_landings(&self, steps: &[isize]) -> Box<[usize]> {
+    self.count(steps, |hit| usize::from(hit.landed))
   }
 
-  count
+  /// Follows `steps` from the current position, counting for each target
+  /// how many clicks point at it, whether a rotation ends there or passes.
+  pub fn count_passes(&self, steps: &[isize]) -> Box<[usize]> {
+    self.count(steps, |hit| hit.passes)
+  }
+}
+
+fn dial(rotations: &Rotations) -> Dial {
+  Dial::new(100, rotations.start, [0])
+}
+
+fn part1(rotations: &Rotations) -> usize {
+  dial(rotations).count_landings(&rotations.steps)[0]
+}
+
+fn part2(rotations: &Rotations) -> usize {
+  dial(rotations).count_passes(&rotations.steps)[0]
+}
+
+/// `size` rotations, each of up to ten turns of the dial.
+fn generate(rng: &mut Rng, size: usize) -> String {
+  let mut input = String::new();
+  for _ in 0..size {
+    let direction = if rng.chance(1, 2) { 'L' } else { 'R' };
+    writeln!(input, "{direction}{}", rng.range(1..=999u16)).unwrap();
+  }
+  input
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+  const DAY: u8 = 1;
+
+  type Input<'a> = Rotations;
+
+  const PARAMS: &'static [Param] =
+    &[Param::number("start", "50").between(0, 99)];
+
+  fn parse<'a>(
+    input: &'a str,
+    params: &Params,
+  ) -> Result<Self::Input<'a>, ParseError> {
+    parse_input(input, params.get("start"))
+  }
+
+  fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+    generate(rng, size)
+  }
+
+  fn part1(input: &Self::Input<'_>) -> Answer {
+    part1(input).into()
+  }
+
+  fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+    Some(part2(input).into())
+  }
+
+  fn reference(input: &Self::Input<'_>, part: Part) -> Option<Answer> {
+    Some(match part {
+      Part::One => reference::part1(input).into(),
+      Part::Two => reference::part2(input).into(),
+    })
+  }
+
+  fn trace(input: &Self::Input<'_>) -> Option<Trace> {
+    let rows = dial(input)
+      .trace(&input.steps)
+      .map(|event| {
+        let hit = event.hits.first();
+        vec![
+          event.index.into(),
+          event.step.into(),
+          event.start.into(),
+          event.end.into(),
+          hit.map_or(0, |hit| hit.passes).into(),
+          hit.is_some_and(|hit| hit.landed).into(),
+        ]
+      })
+      .collect();
+    Some(Trace {
+      columns: vec!["rotation", "step", "start", "end", "passes", "landed"],
+      rows,
+    })
+  }
 }
 
 #[cfg(test)]
@@ -57,17 +275,53 @@ mod tests {
   use super::*;
 
   const INPUT_TEST: &str = include_str!("input_test");
-  const INPUT: &str = include_str!("input");
 
   #[test]
-  fn part1_works() {
-    assert_eq!(part1(INPUT_TEST), 3);
-    assert_eq!(part1(INPUT), 980);
+  fn traces_example() {
+    let rotations = parse_input(INPUT_TEST, 50).unwrap();
+    let events: Vec<_> = dial(&rotations).trace(&rotations.steps).collect();
+
+    let positions: Vec<_> = events.iter().map(|event| event.end).collect();
+    assert_eq!(positions, [82, 52, 0, 95, 55, 0, 99, 0, 14, 32]);
+    assert_eq!(
+      events[0],
+      Event {
+        index: 0,
+        step: -68,
+        start: 50,
+        end: 82,
+        hits: vec![Hit {
+          target: 0,
+          passes: 1,
+          landed: false,
+        }],
+      }
+    );
+    assert_eq!(events[1].hits, []);
+    assert!(events[2].hits[0].landed);
   }
 
   #[test]
-  fn part2_works() {
-    assert_eq!(part2(INPUT_TEST), 6);
-    assert_eq!(part2(INPUT), 5961);
+  fn part2_counts_long_rotations() {
+    let rotations = parse_input("R9000000000000\nL50\nL200\n", 50).unwrap();
+    assert_eq!(part2(&rotations), 90_000_000_003);
+
+    // Starting on, landing on and leaving 0 in either direction.
+    for start in [0, 1, 50, 99] {
+      let rotations =
+        parse_input("L250\nR1\nL1\nR100\nL0\nR199\nL99\n", start).unwrap();
+      assert_eq!(part2(&rotations), reference::part2(&rotations));
+    }
+  }
+
+  #[test]
+  fn dial_counts_each_target() {
+    let mut dial = Dial::new(10, 3, [0, 3, 7]);
+    let steps = [4, -14, 20, -1];
+    assert_eq!(*dial.count_landings(&steps), [0, 2, 1]);
+    assert_eq!(*dial.count_passes(&steps), [3, 4, 4]);
+
+    dial.turn(-14);
+    assert_eq!(dial.position(), 9);
   }
 }
@@ -0,0 +1,29 @@
+//! Turns the dial one click at a time.
+
+use super::Rotations;
+
+pub fn part1(rotations: &Rotations) -> usize {
+  let mut dial = rotations.start;
+  rotations
+    .steps
+    .iter()
+    .filter(|&&step| {
+      dial = (dial + step).rem_euclid(100);
+      dial == 0
+    })
+    .count()
+}
+
+pub fn part2(rotations: &Rotations) -> usize {
+  let mut dial = rotations.start;
+  let mut count = 0;
+  for &step in &rotations.steps {
+    for _ in 0..step.abs() {
+      dial = (dial + step.signum()).rem_euclid(100);
+      if dial == 0 {
+        count += 1;
+      }
+    }
+  }
+  count
+}
@@ -1,78 +1,296 @@
-use std::ops::RangeInclusive;
-
-fn parse_input(input: &str) -> impl Iterator<Item = RangeInclusive<u64>> {
-  input.split(',').map(|range| {
-    let (start, end) = range.trim().split_once('-').unwrap();
-    let start = start.parse().unwrap();
-    let end = end.parse().unwrap();
-    start..=end
-  })
+mod reference;
+
+use std::{iter, ops::RangeInclusive};
+
+use crate::{
+  Answer, ParseError, Part, Solution,
+  params::{Param, Params},
+  parse::Cursor,
+  rng::Rng,
+};
+
+pub struct Ranges {
+  ranges: Box<[RangeInclusive<u128>]>,
+  /// The base the IDs are written in, which is also the one their digits
+  /// repeat in.
+  radix: u32,
 }
 
-fn is_invalid_id(mut id: u64, reps: u32) -> bool {
-  let digits = 1 + id.ilog10();
-  if !digits.is_multiple_of(reps) {
-    return false;
-  }
+fn parse_input(input: &str, radix: u32) -> Result<Ranges, ParseError> {
+  let mut starts = Vec::new();
+  let ranges = Cursor::new(Puzzle::DAY, input)
+    .split(',')
+    .map(|range| {
+      let mut range = range.trim();
+      starts.push(range);
+      let start = range.number_in(radix)?;
+      range.expect('-')?;
+      let end = range.number_in(radix)?;
+      range.end()?;
+      Ok(start..=end)
+    })
+    .collect::<Result<_, _>>()?;
 
-  let quot = 10u64.pow(digits / reps);
+  let ranges = Ranges { ranges, radix };
 
-  for _ in 0..reps - 1 {
-    let lo = id % quot;
-    id /= quot;
-    let hi = id % quot;
-    if hi != lo {
-      return false;
+  // Both answers are `u128`s, so check here that neither overflows.
+  for total in [part1_total(&ranges), part2_total(&ranges)] {
+    if let Err(range) = total {
+      return Err(
+        starts[range].error("a range whose invalid IDs add up to a u128"),
+      );
     }
   }
 
-  true
+  Ok(ranges)
 }
 
-pub fn part1(input: &str) -> u64 {
-  let mut count = 0;
+/// Sums the IDs in `range` that spell a block of `len` digits `reps` times.
+/// Those are the blocks times `mult`, a one followed by `reps - 1` ones each
+/// `len` digits apart, so this sums an arithmetic series of blocks. Returns
+/// `None` if the sum overflows.
+fn sum_repeated(
+  range: &RangeInclusive<u128>,
+  radix: u32,
+  len: u32,
+  reps: u32,
+) -> Option<u128> {
+  let unit = u128::from(radix).pow(len);
+  let Some(mult) =
+    (0..reps).try_fold(0u128, |mult, _| mult.checked_mul(unit)?.checked_add(1))
+  else {
+    // Even the smallest such ID is out of range.
+    return Some(0);
+  };
 
-  for range in parse_input(input) {
-    for id in range {
-      if is_invalid_id(id, 2) {
-        count += id;
-      }
-    }
+  let first = range.start().div_ceil(mult).max(unit / u128::from(radix));
+  let last = (range.end() / mult).min(unit - 1);
+  if first > last {
+    return Some(0);
   }
 
-  count
+  // One of the count and the sum of the ends is even, so halve that one.
+  let count = last - first + 1;
+  let blocks = if count.is_multiple_of(2) {
+    (count / 2).checked_mul(first + last)?
+  } else {
+    count.checked_mul(first.midpoint(last))?
+  };
+  mult.checked_mul(blocks)
 }
 
-pub fn part2(input: &str) -> u64 {
-  let mut count = 0;
+/// Sums the IDs of `digits` digits in `range` that repeat any block, each
+/// once. An ID whose shortest block has `len` digits also repeats every
+/// multiple of that block, so those are subtracted from the longer blocks.
+fn sum_any_repeated(
+  range: &RangeInclusive<u128>,
+  radix: u32,
+  digits: u32,
+) -> Option<u128> {
+  let divisors = |n: u32| (1..n).filter(move |len| n.is_multiple_of(*len));
+
+  // `exact[len]` sums the IDs whose shortest block has `len` digits.
+  let mut exact = vec![0; digits as usize];
+  for len in divisors(digits) {
+    let shorter = divisors(len).try_fold(0u128, |shorter, len| {
+      shorter.checked_add(exact[len as usize])
+    })?;
+    exact[len as usize] =
+      sum_repeated(range, radix, len, digits / len)? - shorter;
+  }
+
+  exact.into_iter().try_fold(0u128, u128::checked_add)
+}
+
+/// Adds up `sum` over every range and every digit count an ID can have, or
+/// returns the index of the range at which the total overflows a `u128`.
+fn total(
+  input: &Ranges,
+  sum: impl Fn(&RangeInclusive<u128>, u32) -> Option<u128>,
+) -> Result<u128, usize> {
+  let max_digits = u128::MAX.ilog(input.radix.into()) + 1;
+  input
+    .ranges
+    .iter()
+    .enumerate()
+    .try_fold(0u128, |total, (i, range)| {
+      (2..=max_digits)
+        .try_fold(total, |total, digits| {
+          total.checked_add(sum(range, digits)?)
+        })
+        .ok_or(i)
+    })
+}
 
-  for range in parse_input(input) {
-    for id in range {
-      if (2..8).any(|reps| is_invalid_id(id, reps)) {
-        count += id;
-      }
+fn part1_total(input: &Ranges) -> Result<u128, usize> {
+  total(input, |range, digits| {
+    if digits.is_multiple_of(2) {
+      sum_repeated(range, input.radix, digits / 2, 2)
+    } else {
+      Some(0)
     }
+  })
+}
+
+fn part2_total(input: &Ranges) -> Result<u128, usize> {
+  total(input, |range, digits| {
+    sum_any_repeated(range, input.radix, digits)
+  })
+}
+
+fn part1(input: &Ranges) -> u128 {
+  part1_total(input).expect("parse_input checks for overflow")
+}
+
+fn part2(input: &Ranges) -> u128 {
+  part2_total(input).expect("parse_input checks for overflow")
+}
+
+/// `id` written out in `radix`, without leading zeros.
+fn spell(mut id: u128, radix: u32) -> String {
+  let mut digits: Vec<char> = iter::from_fn(|| {
+    (id > 0).then(|| {
+      let digit = (id % u128::from(radix)).try_into().unwrap();
+      id /= u128::from(radix);
+      char::from_digit(digit, radix).unwrap()
+    })
+  })
+  .collect();
+  if digits.is_empty() {
+    digits.push('0');
+  }
+  digits.iter().rev().collect()
+}
+
+/// `size` ranges of up to a thousand IDs each, on one line, written in
+/// `radix`.
+fn generate(rng: &mut Rng, size: usize, radix: u32) -> String {
+  let base = u64::from(radix);
+  let ranges: Vec<_> = (0..size.max(1))
+    .map(|_| {
+      let digits = rng.range(1..=10u32);
+      let start = rng.range(base.pow(digits - 1)..=base.pow(digits) - 1);
+      let end = start + rng.range(0..=1000);
+      format!(
+        "{}-{}",
+        spell(start.into(), radix),
+        spell(end.into(), radix)
+      )
+    })
+    .collect();
+  ranges.join(",") + "\n"
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+  const DAY: u8 = 2;
+
+  type Input<'a> = Ranges;
+
+  const PARAMS: &'static [Param] =
+    &[Param::number("radix", "10").between(2, 36)];
+
+  fn parse<'a>(
+    input: &'a str,
+    params: &Params,
+  ) -> Result<Self::Input<'a>, ParseError> {
+    parse_input(input, params.get("radix"))
+  }
+
+  fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+    generate(rng, size, params.get("radix"))
+  }
+
+  fn generate_params(rng: &mut Rng) -> Params {
+    let radix = rng.range(2..=36u32).to_string();
+    Params::resolve(Self::PARAMS, [("radix", radix.as_str())])
+      .expect("radixes from 2 to 36 are allowed")
   }
 
-  count
+  fn part1(input: &Self::Input<'_>) -> Answer {
+    part1(input).into()
+  }
+
+  fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+    Some(part2(input).into())
+  }
+
+  fn reference(input: &Self::Input<'_>, part: Part) -> Option<Answer> {
+    Some(match part {
+      Part::One => reference::part1(input).into(),
+      Part::Two => reference::part2(input).into(),
+    })
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
-  const INPUT_TEST: &str = include_str!("input_test");
-  const INPUT: &str = include_str!("input");
+  #[test]
+  fn sums_wide_ranges() {
+    let ranges = parse_input("1-99999999999\n", 10).unwrap();
+    // 11 + 22 + ... + 99, then 1010 + ... + 9999 and so on, each block
+    // sum times 10^len + 1.
+    let expected: u128 = (1..=5)
+      .map(|len| {
+        let (first, last) = (10u128.pow(len - 1), 10u128.pow(len) - 1);
+        (10u128.pow(len) + 1) * (first + last) * (last - first + 1) / 2
+      })
+      .sum();
+    assert_eq!(part1(&ranges), expected);
+
+    // 111111 repeats blocks of one, two and three digits, but counts once.
+    let ranges = parse_input("111111-111111,99-111\n", 10).unwrap();
+    assert_eq!(part2(&ranges), 111_111 + 99 + 111);
+    assert_eq!(part2(&ranges), reference::part2(&ranges));
+
+    // Twenty ones take ten repeats of two digits, among others.
+    let ranges =
+      parse_input("11111111111111111111-11111111111111111111\n", 10).unwrap();
+    assert_eq!(part2(&ranges), 11_111_111_111_111_111_111);
+  }
 
   #[test]
-  fn part1_works() {
-    assert_eq!(part1(INPUT_TEST), 1_227_775_554);
-    assert_eq!(part1(INPUT), 41_294_979_841);
+  fn sums_other_radixes() {
+    // 0x1f1f, and every hex digit repeated four times from 0x1111 to 0x9999.
+    let ranges = parse_input("1f1f-1f1f,1000-9999\n", 16).unwrap();
+    assert_eq!(part1(&ranges), reference::part1(&ranges));
+    assert_eq!(part2(&ranges), reference::part2(&ranges));
+    assert_eq!(part2(&parse_input("fff-fff\n", 16).unwrap()), 0xfff);
+
+    let ranges = parse_input("1-1111111\n", 2).unwrap();
+    assert_eq!(part2(&ranges), reference::part2(&ranges));
+
+    // The widest IDs there are, in the narrowest radix.
+    let max = u128::MAX;
+    let ranges = parse_input(&format!("{max:b}-{max:b}\n"), 2).unwrap();
+    assert_eq!(part2(&ranges), max);
   }
 
   #[test]
-  fn part2_works() {
-    assert_eq!(part2(INPUT_TEST), 4_174_379_265);
-    assert_eq!(part2(INPUT), 66_500_947_346);
+  fn rejects_only_overflow() {
+    let max = u128::MAX;
+    let Err(err) = parse_input(&format!("1-9,0-{max}\n"), 10) else {
+      panic!("every invalid ID up to u128::MAX should overflow");
+    };
+    assert_eq!(
+      err.to_string(),
+      "day02 line 1 col 5: expected a range whose invalid IDs add up to a \
+       u128, found '0'"
+    );
+
+    // Blocks of 2, 10 and 25 digits repeat in both ends of this range, and
+    // the sums of shorter blocks overflow before anything is subtracted.
+    let range = format!("{}-{}\n", "21".repeat(25), "21212".repeat(10));
+    let Err(err) = parse_input(&range, 6) else {
+      panic!("the sums of shorter blocks should overflow");
+    };
+    assert_eq!(
+      err.to_string(),
+      "day02 line 1 col 1: expected a range whose invalid IDs add up to a \
+       u128, found '2'"
+    );
   }
 }
@@ -0,0 +1,45 @@
+//! Checks the digits of every ID.
+
+use super::Ranges;
+
+/// Whether `id`, written in `radix`, is one block of digits repeated `reps`
+/// times.
+fn is_invalid_id(mut id: u128, radix: u32, reps: u32) -> bool {
+  let digits = id.checked_ilog(radix.into()).map_or(1, |log| log + 1);
+  if !digits.is_multiple_of(reps) {
+    return false;
+  }
+
+  let quot = u128::from(radix).pow(digits / reps);
+
+  for _ in 0..reps - 1 {
+    let lo = id % quot;
+    id /= quot;
+    let hi = id % quot;
+    if hi != lo {
+      return false;
+    }
+  }
+
+  true
+}
+
+fn sum_ids(input: &Ranges, is_invalid: impl Fn(u128) -> bool) -> u128 {
+  input
+    .ranges
+    .iter()
+    .flat_map(Clone::clone)
+    .filter(|&id| is_invalid(id))
+    .sum()
+}
+
+pub fn part1(input: &Ranges) -> u128 {
+  sum_ids(input, |id| is_invalid_id(id, input.radix, 2))
+}
+
+pub fn part2(input: &Ranges) -> u128 {
+  let max_digits = u128::MAX.ilog(input.radix.into()) + 1;
+  sum_ids(input, |id| {
+    (2..=max_digits).any(|reps| is_invalid_id(id, input.radix, reps))
+  })
+}
@@ -1,13 +1,46 @@
+mod reference;
+
 use std::collections::VecDeque;
 
-fn parse_input(input: &str) -> impl Iterator<Item = Box<[u64]>> {
-  input.lines().map(|line| {
-    line
-      .chars()
-      .map(|char| char.to_digit(10).unwrap().into())
-      .collect::<Vec<_>>()
-      .into_boxed_slice()
-  })
+use crate::{
+  Answer, ParseError, Part, Solution,
+  params::{Param, Params},
+  parse::Cursor,
+  rng::Rng,
+};
+
+pub struct Banks {
+  banks: Box<[Box<[u64]>]>,
+  /// How many batteries part 2 turns on in each bank.
+  digits: u32,
+}
+
+fn parse_input(input: &str, digits: u32) -> Result<Banks, ParseError> {
+  let mut starts = Vec::new();
+  let banks = Cursor::new(Puzzle::DAY, input)
+    .lines()
+    .map(|mut line| {
+      starts.push(line);
+      let mut digits = Vec::new();
+      while !line.is_empty() {
+        digits.push(line.digit(10)?.into());
+      }
+      Ok(digits.into_boxed_slice())
+    })
+    .collect::<Result<_, _>>()?;
+
+  let banks = Banks { banks, digits };
+
+  // Both answers are `u64`s, so check here that neither overflows.
+  for total in [total(&banks, 2), total(&banks, digits)] {
+    if let Err(bank) = total {
+      return Err(
+        starts[bank].error("a bank whose joltage keeps the total in a u64"),
+      );
+    }
+  }
+
+  Ok(banks)
 }
 
 fn find_best_value(digits: &[u64], initial_depth: u32) -> u64 {
@@ -19,6 +52,11 @@ fn find_best_value(digits: &[u64], initial_depth: u32) -> u64 {
 
   let mut best_value = 0u64;
 
+  // A bank too short to turn on enough batteries adds nothing.
+  if digits.len() < initial_depth as usize {
+    return best_value;
+  }
+
   let mut queue = VecDeque::<Entry>::new();
 
   queue.push_back(Entry {
@@ -35,8 +73,13 @@ fn find_best_value(digits: &[u64], initial_depth: u32) -> u64 {
   {
     let level = 10u64.pow(depth);
 
-    for next_index in index..(digits.len() - depth as usize) {
-      let next_value = value + level * digits[next_index];
+    for (next_index, digit) in digits
+      .iter()
+      .enumerate()
+      .take(digits.len() - depth as usize)
+      .skip(index)
+    {
+      let next_value = value + level * digit;
 
       if next_value / level < best_value / level {
         continue;
@@ -59,34 +102,73 @@ fn find_best_value(digits: &[u64], initial_depth: u32) -> u64 {
   best_value
 }
 
-pub fn part1(input: &str) -> u64 {
-  parse_input(input)
-    .map(|digits| find_best_value(&digits, 2))
-    .sum()
+/// Adds up the best joltage of `digits` batteries in every bank, or returns
+/// the index of the bank at which the total overflows a `u64`.
+fn total(banks: &Banks, digits: u32) -> Result<u64, usize> {
+  banks
+    .banks
+    .iter()
+    .enumerate()
+    .try_fold(0u64, |total, (i, bank)| {
+      total.checked_add(find_best_value(bank, digits)).ok_or(i)
+    })
 }
 
-pub fn part2(input: &str) -> u64 {
-  parse_input(input)
-    .map(|digits| find_best_value(&digits, 12))
-    .sum()
+fn part1(banks: &Banks) -> u64 {
+  total(banks, 2).expect("parse_input checks for overflow")
 }
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+fn part2(banks: &Banks) -> u64 {
+  total(banks, banks.digits).expect("parse_input checks for overflow")
+}
 
-  const INPUT_TEST: &str = include_str!("input_test");
-  const INPUT: &str = include_str!("input");
+/// `size` banks of at least twelve batteries.
+fn generate(rng: &mut Rng, size: usize) -> String {
+  let mut input = String::new();
+  for _ in 0..size.max(1) {
+    for _ in 0..rng.range(12..=100usize) {
+      input.push(char::from(b'0' + rng.range(1..=9u8)));
+    }
+    input.push('\n');
+  }
+  input
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+  const DAY: u8 = 3;
+
+  type Input<'a> = Banks;
+
+  /// Beyond 19 digits, a single joltage no longer fits in a `u64`, and
+  /// `parse_input` checks that their total does.
+  const PARAMS: &'static [Param] =
+    &[Param::number("digits", "12").between(1, 19)];
+
+  fn parse<'a>(
+    input: &'a str,
+    params: &Params,
+  ) -> Result<Self::Input<'a>, ParseError> {
+    parse_input(input, params.get("digits"))
+  }
+
+  fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+    generate(rng, size)
+  }
+
+  fn part1(input: &Self::Input<'_>) -> Answer {
+    part1(input).into()
+  }
 
-  #[test]
-  fn part1_works() {
-    assert_eq!(part1(INPUT_TEST), 357);
-    assert_eq!(part1(INPUT), 16_812);
+  fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+    Some(part2(input).into())
   }
 
-  #[test]
-  fn part2_works() {
-    assert_eq!(part2(INPUT_TEST), 3_121_910_778_619);
-    assert_eq!(part2(INPUT), 166_345_822_896_410);
+  fn reference(input: &Self::Input<'_>, part: Part) -> Option<Answer> {
+    Some(match part {
+      Part::One => reference::part1(input).into(),
+      Part::Two => reference::part2(input).into(),
+    })
   }
 }
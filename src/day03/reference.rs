@@ -0,0 +1,31 @@
+//! Dynamic programming over suffixes, rather than a pruned search.
+
+use super::Banks;
+
+fn best_value(digits: &[u64], len: usize) -> u64 {
+  // best[k] is the largest value made of k digits from the suffix so far.
+  let mut best = vec![None; len + 1];
+  best[0] = Some(0);
+  for &digit in digits.iter().rev() {
+    for k in (1..=len).rev() {
+      if let Some(rest) = best[k - 1] {
+        let value = digit * 10u64.pow((k - 1).try_into().unwrap()) + rest;
+        best[k] = best[k].max(Some(value));
+      }
+    }
+  }
+  best[len].unwrap_or(0)
+}
+
+pub fn part1(banks: &Banks) -> u64 {
+  banks.banks.iter().map(|digits| best_value(digits, 2)).sum()
+}
+
+pub fn part2(banks: &Banks) -> u64 {
+  let len = banks.digits.try_into().unwrap();
+  banks
+    .banks
+    .iter()
+    .map(|digits| best_value(digits, len))
+    .sum()
+}
@@ -1,134 +1,117 @@
-fn parse_input(input: &str) -> (Box<[u8]>, usize, usize) {
-  let mut lines = input.lines().peekable();
-  let width = lines.peek().unwrap().len();
-  let bytes = lines
-    .flat_map(|line| line.bytes())
-    .collect::<Vec<_>>()
-    .into_boxed_slice();
-  let height = bytes.len() / width;
-  (bytes, width, height)
-}
+mod reference;
 
-fn neighbors(
-  index: usize,
-  width: usize,
-  height: usize,
-) -> impl Iterator<Item = usize> {
-  const NEIGHBORS: [[isize; 2]; 8] = [
-    [-1, -1],
-    [-1, 0],
-    [-1, 1],
-    [0, -1],
-    [0, 1],
-    [1, -1],
-    [1, 0],
-    [1, 1],
-  ];
-
-  let x = (index % width).cast_signed();
-  let y = (index / width).cast_signed();
-
-  NEIGHBORS.into_iter().filter_map(move |[dx, dy]| {
-    let next_x = x + dx;
-    if 0 <= next_x {
-      let next_y = y + dy;
-      if 0 <= next_y {
-        let next_x = next_x.cast_unsigned();
-        if next_x < width {
-          let next_y = next_y.cast_unsigned();
-          if next_y < height {
-            let next_index = width * next_y + next_x;
-            return Some(next_index);
-          }
-        }
-      }
-    }
+use std::fmt;
 
-    None
-  })
-}
+use crate::{
+  Answer, ParseError, Part, Solution, grid::Grid, params::Params,
+  parse::Cursor, rng::Rng,
+};
 
-const PAPER: u8 = b'@';
-
-pub fn part1(input: &str) -> usize {
-  let (bytes, width, height) = parse_input(input);
-
-  let mut accessible_papers_count = 0;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+  Paper,
+  Empty,
+}
 
-  for (index, &byte) in bytes.iter().enumerate() {
-    if byte == PAPER {
-      let mut paper_neighbor_count = 0;
+impl fmt::Display for Tile {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      Tile::Paper => "@",
+      Tile::Empty => ".",
+    })
+  }
+}
 
-      for next_index in neighbors(index, width, height) {
-        let next_byte = bytes[next_index];
-        if next_byte == PAPER {
-          paper_neighbor_count += 1;
-        }
-      }
+fn parse_input(input: &str) -> Result<Grid<Tile>, ParseError> {
+  Grid::parse(Cursor::new(Puzzle::DAY, input), "'@' or '.'", |c| match c {
+    '@' => Some(Tile::Paper),
+    '.' => Some(Tile::Empty),
+    _ => None,
+  })
+}
 
-      if paper_neighbor_count < 4 {
-        accessible_papers_count += 1;
-      }
-    }
-  }
+fn is_accessible(grid: &Grid<Tile>, pos: (usize, usize)) -> bool {
+  grid[pos] == Tile::Paper
+    && grid
+      .neighbors8(pos)
+      .filter(|&next_pos| grid[next_pos] == Tile::Paper)
+      .count()
+      < 4
+}
 
-  accessible_papers_count
+fn part1(grid: &Grid<Tile>) -> usize {
+  grid
+    .positions()
+    .filter(|&pos| is_accessible(grid, pos))
+    .count()
 }
 
-pub fn part2(input: &str) -> usize {
-  let (mut bytes, width, height) = parse_input(input);
+fn part2(grid: &Grid<Tile>) -> usize {
+  let mut grid = grid.clone();
 
   let mut total_removed_papers_count = 0;
 
-  let mut papers_to_remove = Vec::with_capacity(bytes.len());
+  let mut papers_to_remove = Vec::new();
 
   loop {
-    for (index, &byte) in bytes.iter().enumerate() {
-      if byte == PAPER {
-        let mut paper_neighbors_count = 0;
-
-        for next_index in neighbors(index, width, height) {
-          let next_byte = bytes[next_index];
-          if next_byte == PAPER {
-            paper_neighbors_count += 1;
-          }
-        }
-
-        if paper_neighbors_count < 4 {
-          papers_to_remove.push(index);
-        }
-      }
-    }
+    papers_to_remove
+      .extend(grid.positions().filter(|&pos| is_accessible(&grid, pos)));
 
     if papers_to_remove.is_empty() {
       break;
     }
 
-    while let Some(next_index) = papers_to_remove.pop() {
+    while let Some(pos) = papers_to_remove.pop() {
       total_removed_papers_count += 1;
-      bytes[next_index] = b'.';
+      grid[pos] = Tile::Empty;
     }
   }
 
   total_removed_papers_count
 }
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+/// A `size` by `size` grid, two thirds paper.
+fn generate(rng: &mut Rng, size: usize) -> String {
+  let len = size.max(1);
+  let mut grid = Grid::new(len, len, Tile::Empty);
+  for tile in grid.cells_mut() {
+    if rng.chance(2, 3) {
+      *tile = Tile::Paper;
+    }
+  }
+  grid.to_string()
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+  const DAY: u8 = 4;
 
-  const INPUT_TEST: &str = include_str!("input_test");
-  const INPUT: &str = include_str!("input");
+  type Input<'a> = Grid<Tile>;
+
+  fn parse<'a>(
+    input: &'a str,
+    _params: &Params,
+  ) -> Result<Self::Input<'a>, ParseError> {
+    parse_input(input)
+  }
+
+  fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+    generate(rng, size)
+  }
+
+  fn part1(input: &Self::Input<'_>) -> Answer {
+    part1(input).into()
+  }
 
-  #[test]
-  fn part1_works() {
-    assert_eq!(part1(INPUT_TEST), 13);
-    assert_eq!(part1(INPUT), 1460);
+  fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+    Some(part2(input).into())
   }
 
-  #[test]
-  fn part2_works() {
-    assert_eq!(part2(INPUT_TEST), 43);
-    assert_eq!(part2(INPUT), 9243);
+  fn reference(input: &Self::Input<'_>, part: Part) -> Option<Answer> {
+    Some(match part {
+      Part::One => reference::part1(input).into(),
+      Part::Two => reference::part2(input).into(),
+    })
   }
 }
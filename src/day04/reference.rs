@@ -0,0 +1,51 @@
+//! Works on nested vectors, removing one roll at a time.
+
+use super::Tile;
+use crate::grid::Grid;
+
+fn rows(grid: &Grid<Tile>) -> Vec<Vec<bool>> {
+  grid
+    .rows()
+    .map(|row| row.iter().map(|&tile| tile == Tile::Paper).collect())
+    .collect()
+}
+
+fn is_accessible(rows: &[Vec<bool>], x: usize, y: usize) -> bool {
+  let mut neighbors = 0;
+  for ny in y.saturating_sub(1)..=y + 1 {
+    for nx in x.saturating_sub(1)..=x + 1 {
+      if (nx, ny) != (x, y)
+        && rows.get(ny).and_then(|row| row.get(nx)) == Some(&true)
+      {
+        neighbors += 1;
+      }
+    }
+  }
+  rows[y][x] && neighbors < 4
+}
+
+fn accessible(rows: &[Vec<bool>]) -> Vec<(usize, usize)> {
+  let mut found = Vec::new();
+  for (y, row) in rows.iter().enumerate() {
+    for x in 0..row.len() {
+      if is_accessible(rows, x, y) {
+        found.push((x, y));
+      }
+    }
+  }
+  found
+}
+
+pub fn part1(grid: &Grid<Tile>) -> usize {
+  accessible(&rows(grid)).len()
+}
+
+pub fn part2(grid: &Grid<Tile>) -> usize {
+  let mut rows = rows(grid);
+  let mut removed = 0;
+  while let Some(&(x, y)) = accessible(&rows).first() {
+    rows[y][x] = false;
+    removed += 1;
+  }
+  removed
+}
@@ -1,31 +1,55 @@
-use std::ops::RangeInclusive;
+mod reference;
 
-fn parse_input(input: &str) -> (Box<[RangeInclusive<u64>]>, Box<[u64]>) {
+use std::{fmt::Write, ops::RangeInclusive};
+
+use crate::{
+  Answer, ParseError, Part, Solution, params::Params, parse::Cursor, rng::Rng,
+};
+
+type Input = (Box<[RangeInclusive<u64>]>, Box<[u64]>);
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
   let mut ranges = Vec::<RangeInclusive<_>>::new();
   let mut ids = Vec::<_>::new();
+  let mut last_range = None;
 
-  for line in input.lines() {
-    if let Some((left, right)) = line.split_once('-') {
-      ranges.push(left.parse().unwrap()..=right.parse().unwrap());
+  for mut line in Cursor::new(Puzzle::DAY, input).lines() {
+    if line.rest().contains('-') {
+      last_range = Some(line);
+      let start = line.number()?;
+      line.expect('-')?;
+      let end = line.number()?;
+      line.end()?;
+      ranges.push(start..=end);
     } else if !line.is_empty() {
-      ids.push(line.parse().unwrap());
+      ids.push(line.number()?);
+      line.end()?;
     }
   }
 
-  (ranges.into(), ids.into())
-}
+  let input = (ranges.into(), ids.into());
 
-pub fn part1(input: &str) -> usize {
-  let (ranges, ids) = parse_input(input);
+  // Only every ID there is, 0 to `u64::MAX`, is too many to count.
+  if let (None, Some(line)) = (part2_total(&input), last_range) {
+    return Err(line.error("ranges that leave at least one ID spoiled"));
+  }
 
+  Ok(input)
+}
+
+fn part1((ranges, ids): &Input) -> usize {
   ids
-    .into_iter()
+    .iter()
     .filter(|id| ranges.iter().any(|range| range.contains(id)))
     .count()
 }
 
-pub fn part2(input: &str) -> u64 {
-  let (mut ranges, _) = parse_input(input);
+fn part2_total((ranges, _): &Input) -> Option<u64> {
+  let mut ranges: Vec<_> = ranges
+    .iter()
+    .filter(|range| !range.is_empty())
+    .cloned()
+    .collect();
 
   ranges.sort_by_key(|range| *range.start());
 
@@ -33,7 +57,7 @@ pub fn part2(input: &str) -> u64 {
 
   for range in ranges {
     if let Some(last) = merged_ranges.last_mut()
-      && *last.end() >= *range.start() - 1
+      && *last.end() >= range.start().saturating_sub(1)
     {
       *last = *last.start()..=(*last.end()).max(*range.end());
     } else {
@@ -41,28 +65,92 @@ pub fn part2(input: &str) -> u64 {
     }
   }
 
-  merged_ranges
-    .into_iter()
-    .map(|range| range.end() - range.start() + 1)
-    .sum()
+  merged_ranges.into_iter().try_fold(0u64, |total, range| {
+    total.checked_add((range.end() - range.start()).checked_add(1)?)
+  })
 }
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+fn part2(input: &Input) -> u64 {
+  part2_total(input).expect("parse_input checks for overflow")
+}
 
-  const INPUT_TEST: &str = include_str!("input_test");
-  const INPUT: &str = include_str!("input");
+/// `size` overlapping fresh ranges, some of them short ones at either end of
+/// the IDs, then `size` IDs to check.
+fn generate(rng: &mut Rng, size: usize) -> String {
+  let size = size.max(1);
+  let bound = 100 * u64::try_from(size).unwrap();
 
-  #[test]
-  fn part1_works() {
-    assert_eq!(part1(INPUT_TEST), 3);
-    assert_eq!(part1(INPUT), 770);
+  let mut input = String::new();
+  for _ in 0..size {
+    let len = rng.range(0..=bound / 10);
+    let start = if rng.chance(1, 5) {
+      0
+    } else if rng.chance(1, 4) {
+      u64::MAX - len
+    } else {
+      rng.range(1..=bound)
+    };
+    writeln!(input, "{start}-{}", start + len).unwrap();
+  }
+  input.push('\n');
+  for _ in 0..size {
+    let id = if rng.chance(1, 5) {
+      u64::MAX - rng.range(0..=bound / 10)
+    } else {
+      rng.range(0..=bound + bound / 10)
+    };
+    writeln!(input, "{id}").unwrap();
+  }
+  input
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+  const DAY: u8 = 5;
+
+  type Input<'a> = Input;
+
+  fn parse<'a>(
+    input: &'a str,
+    _params: &Params,
+  ) -> Result<Self::Input<'a>, ParseError> {
+    parse_input(input)
   }
 
+  fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+    generate(rng, size)
+  }
+
+  fn part1(input: &Self::Input<'_>) -> Answer {
+    part1(input).into()
+  }
+
+  fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+    Some(part2(input).into())
+  }
+
+  fn reference(input: &Self::Input<'_>, part: Part) -> Option<Answer> {
+    Some(match part {
+      Part::One => reference::part1(input).into(),
+      Part::Two => reference::part2(input).into(),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
   #[test]
-  fn part2_works() {
-    assert_eq!(part2(INPUT_TEST), 14);
-    assert_eq!(part2(INPUT), 357_674_099_117_260);
+  fn rejects_only_overflow() {
+    let max = u64::MAX;
+    assert_eq!(part2(&parse_input(&format!("1-{max}\n")).unwrap()), max);
+    let err = parse_input(&format!("5-{max}\n0-5\n")).unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "day05 line 2 col 1: expected ranges that leave at least one ID \
+       spoiled, found '0'"
+    );
   }
 }
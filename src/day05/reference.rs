@@ -0,0 +1,15 @@
+//! Lists every fresh ingredient ID.
+
+use std::collections::BTreeSet;
+
+use super::Input;
+
+pub fn part1((ranges, ids): &Input) -> usize {
+  let fresh: BTreeSet<u64> = ranges.iter().flat_map(Clone::clone).collect();
+  ids.iter().filter(|id| fresh.contains(id)).count()
+}
+
+pub fn part2((ranges, _): &Input) -> u64 {
+  let fresh: BTreeSet<u64> = ranges.iter().flat_map(Clone::clone).collect();
+  fresh.len().try_into().unwrap()
+}
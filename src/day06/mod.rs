@@ -1,4 +1,10 @@
-use std::ops::Range;
+mod reference;
+
+use std::{fmt::Write, ops::Range};
+
+use crate::{
+  Answer, ParseError, Part, Solution, params::Params, parse::Cursor, rng::Rng,
+};
 
 #[derive(Debug)]
 enum Operator {
@@ -6,105 +12,234 @@ enum Operator {
   Multiply,
 }
 
-pub fn part1(input: &str) -> u64 {
-  let mut lines = input.lines();
-
-  let operators: Vec<_> = lines
-    .next_back()
-    .unwrap()
-    .split_whitespace()
-    .map(|s| match s {
-      "+" => Operator::Add,
-      "*" => Operator::Multiply,
-      _ => panic!("Unknown operator"),
-    })
-    .collect();
+impl Operator {
+  /// Returns `None` if any value, or the result, overflows a `u64`.
+  fn apply(
+    &self,
+    mut values: impl Iterator<Item = Option<u64>>,
+  ) -> Option<u64> {
+    match self {
+      Operator::Add => {
+        values.try_fold(0u64, |acc, value| acc.checked_add(value?))
+      }
+      Operator::Multiply => {
+        values.try_fold(1u64, |acc, value| acc.checked_mul(value?))
+      }
+    }
+  }
+}
 
-  let rows: Vec<_> = lines
-    .flat_map(|line| line.split_whitespace().map(|s| s.parse().unwrap()))
-    .collect();
+pub struct Worksheet {
+  rows: Box<[Box<[Option<u8>]>]>,
+  problems: Box<[(Operator, Range<usize>)]>,
+}
 
-  operators
-    .iter()
-    .enumerate()
-    .map(|(i, op)| {
-      let col_values = rows.iter().skip(i).step_by(operators.len());
+fn parse_input(input: &str) -> Result<Worksheet, ParseError> {
+  let mut lines = Cursor::new(Puzzle::DAY, input).lines();
 
-      match op {
-        Operator::Add => col_values.sum::<u64>(),
-        Operator::Multiply => col_values.product::<u64>(),
+  let mut operators = lines.expect_back("operators")?;
+  let operators_len = operators.rest().len();
+
+  let mut problems = Vec::<(Operator, Range<usize>)>::new();
+  let mut starts = Vec::new();
+
+  for i in 0..operators_len {
+    let op = match operators.peek() {
+      Some('+') => Operator::Add,
+      Some('*') => Operator::Multiply,
+      Some(' ') => {
+        operators.bump();
+        continue;
       }
+      _ => return Err(operators.error("'+', '*' or ' '")),
+    };
+    starts.push(operators);
+    operators.bump();
+
+    if let Some((_, range)) = problems.last_mut() {
+      range.end = i - 1;
+    }
+
+    problems.push((op, i..operators_len));
+  }
+
+  let mut rows = lines
+    .map(|mut line| {
+      let mut row = Vec::new();
+      while let Some(c) = line.peek() {
+        if c == ' ' {
+          row.push(None);
+          line.bump();
+        } else {
+          row.push(Some(line.digit(10)?));
+        }
+      }
+      Ok(row)
     })
-    .sum()
+    .collect::<Result<Vec<_>, _>>()?;
+
+  // Lines may stop short of their last digit, so pad them all to the widest,
+  // and let the last problem run to its end.
+  let width = rows.iter().map(Vec::len).fold(operators_len, usize::max);
+  for row in &mut rows {
+    row.resize(width, None);
+  }
+  if let Some((_, range)) = problems.last_mut() {
+    range.end = width;
+  }
+
+  let worksheet = Worksheet {
+    rows: rows.into_iter().map(Vec::into_boxed_slice).collect(),
+    problems: problems.into(),
+  };
+
+  // Both answers are `u64`s, so check here that neither overflows.
+  for total in [total(&worksheet, across), total(&worksheet, down)] {
+    if let Err(problem) = total {
+      return Err(
+        starts[problem].error("a problem whose numbers and total fit a u64"),
+      );
+    }
+  }
+
+  Ok(worksheet)
 }
 
-pub fn part2(input: &str) -> u64 {
-  let mut lines = input.lines();
+fn fold_digits(mut digits: impl Iterator<Item = u8>) -> Option<u64> {
+  digits.try_fold(0u64, |acc, digit| {
+    acc.checked_mul(10)?.checked_add(digit.into())
+  })
+}
 
-  let operator_ranges = {
-    let operators = lines.next_back().unwrap().as_bytes();
+/// The numbers of a problem read across its rows, as in part 1.
+fn across(
+  worksheet: &Worksheet,
+  range: Range<usize>,
+) -> impl Iterator<Item = Option<u64>> {
+  worksheet.rows.iter().map(move |row| {
+    fold_digits(row[range.clone()].iter().filter_map(|digit| *digit))
+  })
+}
 
-    let mut operator_it = operators.iter().enumerate();
-    let mut current_group: Option<(Operator, usize)> = None;
+/// The numbers of a problem read down its columns, as in part 2.
+fn down(
+  worksheet: &Worksheet,
+  range: Range<usize>,
+) -> impl Iterator<Item = Option<u64>> {
+  range.map(|i_group| {
+    fold_digits(worksheet.rows.iter().filter_map(|row| row[i_group]))
+  })
+}
 
-    std::iter::from_fn::<(Operator, Range<usize>), _>(move || {
-      for (i, c) in operator_it.by_ref() {
-        let next_op = match c {
-          b'+' => Operator::Add,
-          b'*' => Operator::Multiply,
-          _ => continue,
-        };
+/// Adds up every problem with its numbers read by `numbers`, or returns the
+/// index of the first problem at which anything overflows a `u64`.
+fn total<'w, I: Iterator<Item = Option<u64>>>(
+  worksheet: &'w Worksheet,
+  numbers: impl Fn(&'w Worksheet, Range<usize>) -> I,
+) -> Result<u64, usize> {
+  worksheet.problems.iter().enumerate().try_fold(
+    0u64,
+    |total, (i, (op, range))| {
+      op.apply(numbers(worksheet, range.clone()))
+        .and_then(|result| total.checked_add(result))
+        .ok_or(i)
+    },
+  )
+}
 
-        if let Some((op, i_start)) = current_group.replace((next_op, i)) {
-          return Some((op, i_start..i - 1));
-        }
-      }
+fn part1(worksheet: &Worksheet) -> u64 {
+  total(worksheet, across).expect("parse_input checks for overflow")
+}
+
+fn part2(worksheet: &Worksheet) -> u64 {
+  total(worksheet, down).expect("parse_input checks for overflow")
+}
 
-      if let Some((op, i_start)) = current_group.take() {
-        return Some((op, i_start..operators.len()));
+/// `size` problems of two to four numbers, each aligned to one side of its
+/// column.
+fn generate(rng: &mut Rng, size: usize) -> String {
+  let rows = rng.range(2..=4usize);
+  let mut lines = vec![String::new(); rows + 1];
+
+  for i in 0..size.max(1) {
+    if i != 0 {
+      for line in &mut lines {
+        line.push(' ');
       }
+    }
 
-      None
-    })
-  };
+    let width = rng.range(1..=3usize);
+    let right_aligned = rng.chance(1, 2);
 
-  let rows: Vec<Vec<_>> = lines
-    .map(|line| line.chars().map(|c| c.to_digit(10)).collect())
-    .collect();
-
-  operator_ranges
-    .map(|(op, range)| {
-      let values = range.map(|i_group| {
-        rows
-          .iter()
-          .filter_map(|row| row[i_group])
-          .fold(0u64, |acc, val| 10 * acc + u64::from(val))
-      });
-
-      match op {
-        Operator::Add => values.sum::<u64>(),
-        Operator::Multiply => values.product::<u64>(),
+    for line in &mut lines[..rows] {
+      let mut number = rng.range(1..=9u8).to_string();
+      for _ in 1..rng.range(1..=width) {
+        number.push(char::from(b'0' + rng.range(0..=9u8)));
+      }
+      if right_aligned {
+        write!(line, "{number:>width$}").unwrap();
+      } else {
+        write!(line, "{number:<width$}").unwrap();
       }
+    }
+
+    let op = if rng.chance(1, 2) { '+' } else { '*' };
+    write!(lines[rows], "{op:<width$}").unwrap();
+  }
+
+  lines.join("\n") + "\n"
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+  const DAY: u8 = 6;
+
+  type Input<'a> = Worksheet;
+
+  fn parse<'a>(
+    input: &'a str,
+    _params: &Params,
+  ) -> Result<Self::Input<'a>, ParseError> {
+    parse_input(input)
+  }
+
+  fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+    generate(rng, size)
+  }
+
+  fn part1(input: &Self::Input<'_>) -> Answer {
+    part1(input).into()
+  }
+
+  fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+    Some(part2(input).into())
+  }
+
+  fn reference(input: &Self::Input<'_>, part: Part) -> Option<Answer> {
+    Some(match part {
+      Part::One => reference::part1(input).into(),
+      Part::Two => reference::part2(input).into(),
     })
-    .sum()
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
-  const INPUT_TEST: &str = include_str!("input_test");
-  const INPUT: &str = include_str!("input");
-
-  #[test]
-  fn part1_works() {
-    assert_eq!(part1(INPUT_TEST), 4_277_556);
-    assert_eq!(part1(INPUT), 6_371_789_547_734);
-  }
-
   #[test]
-  fn part2_works() {
-    assert_eq!(part2(INPUT_TEST), 3_263_827);
-    assert_eq!(part2(INPUT), 11_419_862_653_216);
+  fn rejects_only_overflow() {
+    let worksheet = parse_input("12345678901234567890\n+\n").unwrap();
+    assert_eq!(part1(&worksheet), 12_345_678_901_234_567_890);
+
+    let Err(err) = parse_input("1  4294967296\n1  4294967296\n+  *\n") else {
+      panic!("4294967296 squared should overflow a u64");
+    };
+    assert_eq!(
+      err.to_string(),
+      "day06 line 3 col 4: expected a problem whose numbers and total fit a \
+       u64, found '*'"
+    );
   }
 }
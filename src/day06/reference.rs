@@ -0,0 +1,51 @@
+//! Spells each number out as text before parsing it.
+
+use super::{Operator, Worksheet};
+
+fn number(digits: impl Iterator<Item = Option<u8>>) -> u64 {
+  let text: String = digits
+    .flatten()
+    .map(|digit| char::from(b'0' + digit))
+    .collect();
+  text.parse().unwrap_or(0)
+}
+
+fn solve(op: &Operator, numbers: &[u64]) -> u64 {
+  let mut result = match op {
+    Operator::Add => 0,
+    Operator::Multiply => 1,
+  };
+  for &number in numbers {
+    match op {
+      Operator::Add => result += number,
+      Operator::Multiply => result *= number,
+    }
+  }
+  result
+}
+
+pub fn part1(worksheet: &Worksheet) -> u64 {
+  let mut total = 0;
+  for (op, range) in &worksheet.problems {
+    let numbers: Vec<u64> = worksheet
+      .rows
+      .iter()
+      .map(|row| number(range.clone().map(|i| row[i])))
+      .collect();
+    total += solve(op, &numbers);
+  }
+  total
+}
+
+pub fn part2(worksheet: &Worksheet) -> u64 {
+  let mut total = 0;
+  for (op, range) in &worksheet.problems {
+    let numbers: Vec<u64> = range
+      .clone()
+      .rev()
+      .map(|i| number(worksheet.rows.iter().map(|row| row[i])))
+      .collect();
+    total += solve(op, &numbers);
+  }
+  total
+}
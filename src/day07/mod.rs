@@ -1,56 +1,69 @@
-use std::collections::HashMap;
+mod reference;
+
+use std::{collections::HashMap, fmt};
+
+use crate::{
+  Answer, ParseError, Part, Solution,
+  grid::{Grid, Pos},
+  params::Params,
+  parse::Cursor,
+  rng::Rng,
+};
 
 #[derive(Debug, Clone, Copy)]
-enum Node {
+pub enum Node {
   Empty,
   Beam,
   Splitter,
 }
 
-fn parse_input(input: &str) -> Box<[Box<[Node]>]> {
-  input
-    .lines()
-    .map(|line| {
-      line
-        .chars()
-        .map(|c| match c {
-          '.' => Node::Empty,
-          'S' => Node::Beam,
-          '^' => Node::Splitter,
-          _ => unreachable!("Invalid byte"),
-        })
-        .collect::<Vec<_>>()
-        .into_boxed_slice()
+impl fmt::Display for Node {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      Node::Empty => ".",
+      Node::Beam => "S",
+      Node::Splitter => "^",
     })
-    .collect::<Vec<_>>()
-    .into_boxed_slice()
+  }
 }
 
-pub fn part1(input: &str) -> usize {
-  let mut map = parse_input(input);
+fn parse_input(input: &str) -> Result<Grid<Node>, ParseError> {
+  let cursor = Cursor::new(Puzzle::DAY, input);
 
-  let mut split_count = 0;
+  let map = Grid::parse(cursor, "'.', 'S' or '^'", |c| match c {
+    '.' => Some(Node::Empty),
+    'S' => Some(Node::Beam),
+    '^' => Some(Node::Splitter),
+    _ => None,
+  })?;
+
+  if !map.cells().iter().any(|node| matches!(node, Node::Beam)) {
+    return Err(cursor.at_end().error("'S'"));
+  }
 
-  for y in 1..map.len() {
-    let (before, after) = map.split_at_mut(y);
+  Ok(map)
+}
 
-    let prev = before.last().unwrap();
-    let next = after.first_mut().unwrap();
+fn part1(map: &Grid<Node>) -> usize {
+  let mut map = map.clone();
 
-    for x in 0..prev.len() {
-      if matches!(prev[x], Node::Beam) {
-        match next[x] {
+  let mut split_count = 0;
+
+  for y in 1..map.height() {
+    for x in 0..map.width() {
+      if matches!(map[(x, y - 1)], Node::Beam) {
+        match map[(x, y)] {
           Node::Splitter => {
             split_count += 1;
             if 0 < x {
-              next[x - 1] = Node::Beam;
+              map[(x - 1, y)] = Node::Beam;
             }
-            if x + 1 < next.len() {
-              next[x + 1] = Node::Beam;
+            if x + 1 < map.width() {
+              map[(x + 1, y)] = Node::Beam;
             }
           }
           _ => {
-            next[x] = Node::Beam;
+            map[(x, y)] = Node::Beam;
           }
         }
       }
@@ -60,12 +73,10 @@ pub fn part1(input: &str) -> usize {
   split_count
 }
 
-pub fn part2(input: &str) -> u64 {
-  type Pos = (usize, usize);
-
+fn part2(map: &Grid<Node>) -> u64 {
   fn timelines(
     cache: &mut HashMap<Pos, u64>,
-    map: &[Box<[Node]>],
+    map: &Grid<Node>,
     pos: Pos,
   ) -> u64 {
     if let Some(&count) = cache.get(&pos) {
@@ -76,17 +87,18 @@ pub fn part2(input: &str) -> u64 {
 
     let (x, y) = pos;
 
-    for y in y..map.len() {
-      let row = &map[y];
-      if matches!(row[x], Node::Splitter) {
+    for y in y..map.height() {
+      if matches!(map[(x, y)], Node::Splitter) {
         count += 1;
 
+        // Split beams carry on from the row below, so that neighbouring
+        // splitters cannot pass a beam back and forth.
         if 0 < x {
-          count += timelines(cache, map, (x - 1, y));
+          count += timelines(cache, map, (x - 1, y + 1));
         }
 
-        if x + 1 < row.len() {
-          count += timelines(cache, map, (x + 1, y));
+        if x + 1 < map.width() {
+          count += timelines(cache, map, (x + 1, y + 1));
         }
 
         break;
@@ -98,40 +110,81 @@ pub fn part2(input: &str) -> u64 {
     count
   }
 
-  let map = parse_input(input);
-
   let start_pos = map
     .iter()
-    .enumerate()
-    .find_map(|(y, row)| {
-      row
-        .iter()
-        .enumerate()
-        .find_map(|(x, node)| matches!(node, Node::Beam).then_some((x, y)))
-    })
+    .find_map(|(pos, node)| matches!(node, Node::Beam).then_some(pos))
     .unwrap();
 
   let mut cache = HashMap::new();
 
-  timelines(&mut cache, &map, start_pos) + 1
+  timelines(&mut cache, map, start_pos) + 1
 }
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+/// A manifold `2 * size + 1` wide. Like the real inputs, splitters only sit
+/// on every other row, on alternating columns within reach of the beam. Each
+/// row of splitters can double the timelines, so there are at most 60.
+fn generate(rng: &mut Rng, size: usize) -> String {
+  let half = size.clamp(1, 60);
+  let mut map = Grid::new(2 * half + 1, 2 * half + 2, Node::Empty);
+  map[(half, 0)] = Node::Beam;
+  for reach in 0..half {
+    let y = 2 * reach + 2;
+    for x in (half - reach..=half + reach).step_by(2) {
+      if y == 2 || rng.chance(2, 3) {
+        map[(x, y)] = Node::Splitter;
+      }
+    }
+  }
+  map.to_string()
+}
 
-  const INPUT_TEST: &str = include_str!("input_test");
-  const INPUT: &str = include_str!("input");
+pub struct Puzzle;
 
-  #[test]
-  fn part1_works() {
-    assert_eq!(part1(INPUT_TEST), 21);
-    assert_eq!(part1(INPUT), 1537);
+impl Solution for Puzzle {
+  const DAY: u8 = 7;
+
+  type Input<'a> = Grid<Node>;
+
+  fn parse<'a>(
+    input: &'a str,
+    _params: &Params,
+  ) -> Result<Self::Input<'a>, ParseError> {
+    parse_input(input)
+  }
+
+  fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+    generate(rng, size)
+  }
+
+  fn part1(input: &Self::Input<'_>) -> Answer {
+    part1(input).into()
+  }
+
+  fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+    Some(part2(input).into())
   }
 
+  fn reference(input: &Self::Input<'_>, part: Part) -> Option<Answer> {
+    Some(match part {
+      Part::One => reference::part1(input).into(),
+      Part::Two => reference::part2(input).into(),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
   #[test]
-  fn part2_works() {
-    assert_eq!(part2(INPUT_TEST), 40);
-    assert_eq!(part2(INPUT), 18_818_811_755_665);
+  fn part2_continues_split_beams_below() {
+    // Splitting at (1, 1) used to carry on from (0, 1) and (2, 1), and the
+    // splitter at (2, 1) sent its beam straight back, recursing until the
+    // stack overflowed. Beams only move down, as in the reference.
+    for input in [".S.\n.^^\n...\n", "..S..\n..^..\n.^^^.\n.....\n"] {
+      let map = parse_input(input).unwrap();
+      assert_eq!(part2(&map), reference::part2(&map), "{input}");
+    }
+    assert_eq!(part2(&parse_input(".S.\n.^^\n...\n").unwrap()), 2);
   }
 }
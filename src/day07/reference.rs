@@ -0,0 +1,66 @@
+//! Moves every beam down one row at a time.
+
+use std::collections::BTreeSet;
+
+use super::Node;
+use crate::grid::Grid;
+
+/// Where a beam at `x` goes on meeting a splitter, or `None` where it leaves
+/// the manifold.
+fn split(map: &Grid<Node>, x: usize) -> [Option<usize>; 2] {
+  [x.checked_sub(1), Some(x + 1).filter(|&x| x < map.width())]
+}
+
+pub fn part1(map: &Grid<Node>) -> usize {
+  let mut beams = BTreeSet::new();
+  let mut split_count = 0;
+
+  for (y, row) in map.rows().enumerate() {
+    let mut next = BTreeSet::new();
+    for &x in &beams {
+      if matches!(row[x], Node::Splitter) {
+        split_count += 1;
+        next.extend(split(map, x).into_iter().flatten());
+      } else {
+        next.insert(x);
+      }
+    }
+    next
+      .extend((0..map.width()).filter(|&x| matches!(map[(x, y)], Node::Beam)));
+    beams = next;
+  }
+
+  split_count
+}
+
+/// A beam that leaves the side of the manifold still counts as a timeline.
+pub fn part2(map: &Grid<Node>) -> u64 {
+  let mut timelines = vec![0; map.width()];
+  let mut left = 0;
+  let mut started = false;
+
+  for row in map.rows() {
+    let mut next = vec![0; map.width()];
+    for (x, &count) in timelines.iter().enumerate() {
+      if matches!(row[x], Node::Splitter) {
+        for side in split(map, x) {
+          match side {
+            Some(x) => next[x] += count,
+            None => left += count,
+          }
+        }
+      } else {
+        next[x] += count;
+      }
+    }
+    if !started
+      && let Some(x) = row.iter().position(|node| matches!(node, Node::Beam))
+    {
+      next[x] += 1;
+      started = true;
+    }
+    timelines = next;
+  }
+
+  timelines.iter().sum::<u64>() + left
+}
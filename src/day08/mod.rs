@@ -1,128 +1,146 @@
-use std::cmp::Reverse;
+mod reference;
 
-use bit_set::BitSet;
+use std::{cmp::Reverse, fmt::Write};
 
-type Point = [u64; 3];
+use crate::{
+  Answer, ParseError, Part, Solution, geom,
+  params::{Param, Params},
+  parse::Cursor,
+  rng::Rng,
+  union_find::UnionFind,
+};
 
-fn parse_input(input: &str) -> impl Iterator<Item = Point> {
-  input.lines().map(|line| {
-    let mut it = line.split(',');
-    std::array::from_fn(move |_| it.next().unwrap().parse().unwrap())
-  })
-}
+type Point = geom::Point<u64, 3>;
 
-fn dist_squared([x_a, y_a, z_a]: Point, [x_b, y_b, z_b]: Point) -> u64 {
-  x_a.abs_diff(x_b).pow(2) + y_a.abs_diff(y_b).pow(2) + z_a.abs_diff(z_b).pow(2)
+/// The largest coordinate for which the squared distance between any two
+/// boxes, up to three squared differences, still fits in a `u64`. Part 2
+/// multiplies two coordinates, which this also keeps within a `u64`.
+const MAX_COORD: u64 = (u64::MAX / 3).isqrt();
+
+pub struct Playground {
+  points: Box<[Point]>,
+  /// How many of the closest pairs part 1 connects.
+  connections: usize,
 }
 
-pub fn part1(input: &str, truncate_count: usize) -> usize {
-  let points: Vec<Point> = parse_input(input).collect();
+fn parse_input(
+  input: &str,
+  connections: usize,
+) -> Result<Playground, ParseError> {
+  let cursor = Cursor::new(Puzzle::DAY, input);
+  let points: Box<[Point]> = cursor
+    .lines()
+    .map(|mut line| {
+      let start = line;
+      let point = Point::parse(&mut line, ',')?;
+      if point.0.iter().any(|&coord| coord > MAX_COORD) {
+        return Err(start.error(format!("coordinates up to {MAX_COORD}")));
+      }
+      line.end()?;
+      Ok(point)
+    })
+    .collect::<Result<_, _>>()?;
+
+  if points.len() < 2 {
+    return Err(cursor.at_end().error("at least two junction boxes"));
+  }
+
+  Ok(Playground {
+    points,
+    connections,
+  })
+}
 
-  let mut pairs = (0..points.len())
+fn pairs(points: &[Point]) -> Vec<(usize, usize)> {
+  (0..points.len())
     .flat_map(|i| (i + 1..points.len()).map(move |j| (i, j)))
-    .collect::<Vec<_>>();
-
-  pairs.select_nth_unstable_by_key(truncate_count, |(a, b)| {
-    dist_squared(points[*a], points[*b])
-  });
-  pairs.truncate(truncate_count);
-
-  let mut points_to_groups: Vec<_> = (0..points.len()).collect();
-  let mut groups_to_points: Vec<_> = (0..points.len())
-    .map(|n| {
-      let mut set = BitSet::new();
-      set.insert(n);
-      set
-    })
-    .collect();
+    .collect()
+}
 
-  for (point_id_a, point_id_b) in pairs {
-    let group_id_a = points_to_groups[point_id_a];
-    let group_id_b = points_to_groups[point_id_b];
+fn part1(playground: &Playground) -> usize {
+  let points = &playground.points;
+  let truncate_count = playground.connections;
+  let mut pairs = pairs(points);
 
-    if group_id_a != group_id_b {
-      let group_b = std::mem::take(&mut groups_to_points[group_id_b]);
+  if truncate_count < pairs.len() {
+    pairs.select_nth_unstable_by_key(truncate_count, |(a, b)| {
+      points[*a].dist_squared(points[*b])
+    });
+    pairs.truncate(truncate_count);
+  }
 
-      for point_id in &group_b {
-        points_to_groups[point_id] = group_id_a;
-      }
+  let mut circuits = UnionFind::new(points.len());
 
-      groups_to_points[group_id_a].union_with(&group_b);
-    }
+  for (a, b) in pairs {
+    circuits.union(a, b);
   }
 
-  let mut groups = groups_to_points
-    .into_iter()
-    .map(|group| group.len())
-    .collect::<Vec<_>>();
+  let mut sizes: Vec<_> = circuits.sizes().collect();
 
-  groups.select_nth_unstable_by_key(3, |key| Reverse(*key));
+  sizes.sort_unstable_by_key(|size| Reverse(*size));
 
-  groups.into_iter().take(3).product()
+  sizes.into_iter().take(3).product()
 }
 
-pub fn part2(input: &str) -> u64 {
-  let points: Vec<Point> = parse_input(input).collect();
+fn part2(playground: &Playground) -> u64 {
+  let points = &playground.points;
+  let mut pairs = pairs(points);
 
-  let mut pairs = (0..points.len())
-    .flat_map(|i| (i + 1..points.len()).map(move |j| (i, j)))
-    .collect::<Vec<_>>();
+  pairs.sort_unstable_by_key(|(a, b)| points[*a].dist_squared(points[*b]));
 
-  pairs.sort_unstable_by_key(|(a, b)| dist_squared(points[*a], points[*b]));
+  let mut circuits = UnionFind::new(points.len());
 
-  let mut points_to_groups: Vec<_> = (0..points.len()).collect();
-  let mut groups_to_points: Vec<_> = (0..points.len())
-    .map(|n| {
-      let mut set = BitSet::new();
-      set.insert(n);
-      set
-    })
-    .collect();
+  for (a, b) in pairs {
+    if circuits.union(a, b) && circuits.components() == 1 {
+      return points[a][0] * points[b][0];
+    }
+  }
 
-  let mut group_len = points.len();
+  unreachable!();
+}
 
-  for (point_id_a, point_id_b) in pairs {
-    let group_id_a = points_to_groups[point_id_a];
-    let group_id_b = points_to_groups[point_id_b];
+/// `size` junction boxes scattered through a large cube.
+fn generate(rng: &mut Rng, size: usize) -> String {
+  let mut input = String::new();
+  for _ in 0..size.max(2) {
+    let [x, y, z] = [(); 3].map(|()| rng.range(0..=99_999u64));
+    writeln!(input, "{x},{y},{z}").unwrap();
+  }
+  input
+}
 
-    if group_id_a != group_id_b {
-      let group_b = std::mem::take(&mut groups_to_points[group_id_b]);
+pub struct Puzzle;
 
-      for point_id in &group_b {
-        points_to_groups[point_id] = group_id_a;
-      }
+impl Solution for Puzzle {
+  const DAY: u8 = 8;
 
-      groups_to_points[group_id_a].union_with(&group_b);
+  type Input<'a> = Playground;
 
-      group_len -= 1;
-    }
+  const PARAMS: &'static [Param] = &[Param::number("connections", "1000")];
 
-    if group_len == 1 {
-      let x_a = points[point_id_a][0];
-      let x_b = points[point_id_b][0];
-      return x_a * x_b;
-    }
+  fn parse<'a>(
+    input: &'a str,
+    params: &Params,
+  ) -> Result<Self::Input<'a>, ParseError> {
+    parse_input(input, params.get("connections"))
   }
 
-  unreachable!();
-}
-
-#[cfg(test)]
-mod tests {
-  use super::*;
+  fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+    generate(rng, size)
+  }
 
-  const INPUT_TEST: &str = include_str!("input_test");
-  const INPUT: &str = include_str!("input");
+  fn part1(input: &Self::Input<'_>) -> Answer {
+    part1(input).into()
+  }
 
-  #[test]
-  fn part1_works() {
-    assert_eq!(part1(INPUT_TEST, 10), 40);
-    assert_eq!(part1(INPUT, 1000), 96_672);
+  fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+    Some(part2(input).into())
   }
 
-  #[test]
-  fn part2_works() {
-    assert_eq!(part2(INPUT_TEST), 25_272);
-    assert_eq!(part2(INPUT), 22_517_595);
+  fn reference(input: &Self::Input<'_>, part: Part) -> Option<Answer> {
+    Some(match part {
+      Part::One => reference::part1(input).into(),
+      Part::Two => reference::part2(input).into(),
+    })
   }
 }
@@ -0,0 +1,57 @@
+//! Sorts every pair and relabels whole circuits as they merge.
+
+use super::{Playground, Point};
+
+fn sorted_pairs(points: &[Point]) -> Vec<(usize, usize)> {
+  let mut pairs = Vec::new();
+  for i in 0..points.len() {
+    for j in i + 1..points.len() {
+      pairs.push((i, j));
+    }
+  }
+  pairs.sort_by_key(|&(a, b)| points[a].dist_squared(points[b]));
+  pairs
+}
+
+/// Moves every member of `b`'s circuit into `a`'s, returning whether they
+/// were apart.
+fn connect(labels: &mut [usize], a: usize, b: usize) -> bool {
+  let (from, to) = (labels[b], labels[a]);
+  for label in labels.iter_mut().filter(|label| **label == from) {
+    *label = to;
+  }
+  from != to
+}
+
+pub fn part1(playground: &Playground) -> usize {
+  let points = &playground.points;
+  let mut labels: Vec<usize> = (0..points.len()).collect();
+  for (a, b) in sorted_pairs(points)
+    .into_iter()
+    .take(playground.connections)
+  {
+    connect(&mut labels, a, b);
+  }
+
+  let mut sizes: Vec<usize> = (0..points.len())
+    .map(|label| labels.iter().filter(|&&other| other == label).count())
+    .filter(|&size| size != 0)
+    .collect();
+  sizes.sort_unstable();
+  sizes.iter().rev().take(3).product()
+}
+
+pub fn part2(playground: &Playground) -> u64 {
+  let points = &playground.points;
+  let mut labels: Vec<usize> = (0..points.len()).collect();
+  let mut circuits = points.len();
+  for (a, b) in sorted_pairs(points) {
+    if connect(&mut labels, a, b) {
+      circuits -= 1;
+      if circuits == 1 {
+        return points[a][0] * points[b][0];
+      }
+    }
+  }
+  0
+}
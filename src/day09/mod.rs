@@ -1,41 +1,95 @@
-use std::{array, cmp::Reverse};
+mod reference;
 
-fn parse_input(input: &str) -> impl Iterator<Item = Point> {
-  input.lines().map(|line| {
-    let mut it = line.split(',');
-    array::from_fn(move |_| it.next().unwrap().parse().unwrap())
-  })
-}
+use std::{collections::BTreeSet, fmt::Write, ops::RangeInclusive};
 
-fn area(&[x_a, y_a]: &Point, &[x_b, y_b]: &Point) -> u64 {
-  (x_a.abs_diff(x_b) + 1) * (y_a.abs_diff(y_b) + 1)
-}
+use crate::{
+  Answer, ParseError, Part, Solution,
+  geom::{self, Aabb},
+  params::Params,
+  parse::Cursor,
+  rng::Rng,
+};
+
+type Point = geom::Point<u64, 2>;
 
-type Point = [u64; 2];
-type Line<'a> = [&'a Point; 2];
+/// The largest coordinate for which the area of any rectangle, with sides of
+/// up to `MAX_COORD + 1` tiles, fits in a `u64`.
+const MAX_COORD: u64 = u32::MAX as u64 - 1;
 
-trait CircularWindows<T> {
-  fn circular_windows<'a, const N: usize>(
-    &'a self,
-  ) -> impl Iterator<Item = [&'a T; N]>
-  where
-    T: 'a;
+/// Whether two tiles share a row or a column, as consecutive red tiles must.
+fn in_line(a: Point, b: Point) -> bool {
+  a[0] == b[0] || a[1] == b[1]
 }
 
-impl<T> CircularWindows<T> for [T] {
-  fn circular_windows<'a, const N: usize>(
-    &'a self,
-  ) -> impl Iterator<Item = [&'a T; N]>
-  where
-    T: 'a,
+fn parse_input(input: &str) -> Result<Box<[Point]>, ParseError> {
+  let cursor = Cursor::new(Puzzle::DAY, input);
+  let mut points = Vec::new();
+  let mut starts = Vec::new();
+
+  for mut line in cursor.lines() {
+    let start = line;
+    let point = Point::parse(&mut line, ',')?;
+    if point.0.iter().any(|&coord| coord > MAX_COORD) {
+      return Err(start.error(format!("coordinates up to {MAX_COORD}")));
+    }
+    line.end()?;
+    if points.last().is_some_and(|&last| !in_line(last, point)) {
+      return Err(start.error("a tile in line with the previous one"));
+    }
+    points.push(point);
+    starts.push(start);
+  }
+
+  if let (Some(&first), Some(&last)) = (points.first(), points.last())
+    && !in_line(first, last)
   {
-    (0..self.len()).map(|i| array::from_fn(|n| &self[(i + n) % self.len()]))
+    return Err(cursor.at_end().error("a tile in line with the first one"));
   }
+
+  if points.len() < 4 {
+    return Err(cursor.at_end().error("at least four red tiles"));
+  }
+
+  if let Some(edge) = find_crossing(&points) {
+    // The edge ends on the next tile, or closes the loop at the end.
+    let at = starts.get(edge + 1).map_or(cursor.at_end(), |&start| start);
+    return Err(at.error("an edge that does not cross or touch the loop"));
+  }
+
+  Ok(points.into())
+}
+
+/// Finds an edge that meets an earlier one anywhere but at the tile they
+/// share, so that the loop is not simple. Edges are numbered by the tile
+/// they start from.
+fn find_crossing(points: &[Point]) -> Option<usize> {
+  let edges: Vec<_> = geom::edges(points)
+    .map(|[a, b]| Aabb::from_corners(a, b))
+    .collect();
+  let last = edges.len() - 1;
+
+  (1..edges.len()).find(|&j| {
+    (0..j).any(|i| {
+      let Some(meet) = edges[i].intersection(&edges[j]) else {
+        return false;
+      };
+      let shared = if i + 1 == j {
+        Some(points[j])
+      } else if i == 0 && j == last {
+        Some(points[0])
+      } else {
+        None
+      };
+      shared.is_none_or(|tile| meet != Aabb::from_corners(tile, tile))
+    })
+  })
 }
 
-pub fn part1(input: &str) -> u64 {
-  let points: Vec<Point> = parse_input(input).collect();
+fn area(&a: &Point, &b: &Point) -> u64 {
+  Aabb::from_corners(a, b).area()
+}
 
+fn part1(points: &[Point]) -> u64 {
   points
     .iter()
     .enumerate()
@@ -45,49 +99,211 @@ pub fn part1(input: &str) -> u64 {
     .unwrap()
 }
 
-pub fn part2(input: &str) -> u64 {
-  let points: Vec<Point> = parse_input(input).collect();
+/// The runs of tiles along one axis that every red tile splits it into: each
+/// coordinate a red tile has, and the gaps between them. Edges only start and
+/// end on red tiles, so every tile in a run is filled alike.
+fn runs(coords: impl Iterator<Item = u64>) -> Vec<RangeInclusive<u64>> {
+  let coords: BTreeSet<u64> = coords.collect();
+  let mut runs = Vec::with_capacity(2 * coords.len());
+  let mut coords = coords.into_iter().peekable();
+  while let Some(coord) = coords.next() {
+    runs.push(coord..=coord);
+    if let Some(&next) = coords.peek()
+      && coord + 1 < next
+    {
+      runs.push(coord + 1..=next - 1);
+    }
+  }
+  runs
+}
 
-  let lines: Vec<Line> = points.circular_windows().collect();
+/// Which run a red tile's coordinate is.
+fn run_of(runs: &[RangeInclusive<u64>], coord: u64) -> usize {
+  runs.partition_point(|run| *run.end() < coord)
+}
 
-  let mut pairs: Vec<_> = points
-    .iter()
-    .enumerate()
-    .flat_map(|(i, a)| points[i + 1..].iter().map(move |b| (a, b, area(a, b))))
+/// Fills in the floor one run of tiles at a time, so that the loop is
+/// compared tile by tile however large its coordinates are.
+fn part2(points: &[Point]) -> u64 {
+  let edges: Vec<Aabb<u64, 2>> = geom::edges(points)
+    .map(|[a, b]| Aabb::from_corners(a, b))
     .collect();
+  let columns = runs(points.iter().map(|point| point[0]));
+  let rows = runs(points.iter().map(|point| point[1]));
+
+  // empty[y][x] counts the unfilled runs above and to the left of (x, y).
+  let mut empty = vec![vec![0u32; columns.len() + 1]; rows.len() + 1];
+  for (y, row) in rows.iter().enumerate() {
+    let tile_y = *row.start();
 
-  pairs.sort_unstable_by_key(|(_, _, a)| Reverse(*a));
-
-  pairs
-    .into_iter()
-    .find(|(p1, p2, _)| {
-      lines.iter().all(|[l1, l2]| {
-        p1[0].max(p2[0]) <= l1[0].min(l2[0])
-          || p1[0].min(p2[0]) >= l1[0].max(l2[0])
-          || p1[1].max(p2[1]) <= l1[1].min(l2[1])
-          || p1[1].min(p2[1]) >= l1[1].max(l2[1])
-      })
+    // Which columns hold an edge, counted as the difference from the column
+    // before, and which a vertical edge crosses the row at, so that the
+    // columns after it swap between outside and inside.
+    let mut on_edge = vec![0i32; columns.len() + 1];
+    let mut crossings = vec![false; columns.len()];
+    for edge in &edges {
+      if edge.min[1] <= tile_y && tile_y <= edge.max[1] {
+        on_edge[run_of(&columns, edge.min[0])] += 1;
+        on_edge[run_of(&columns, edge.max[0]) + 1] -= 1;
+      }
+      if edge.min[0] == edge.max[0]
+        && edge.min[1] <= tile_y
+        && tile_y < edge.max[1]
+      {
+        crossings[run_of(&columns, edge.min[0])] ^= true;
+      }
+    }
+
+    let (mut edges_here, mut inside) = (0, false);
+    for x in 0..columns.len() {
+      edges_here += on_edge[x];
+      inside ^= crossings[x];
+      let filled = edges_here > 0 || inside;
+      empty[y + 1][x + 1] =
+        empty[y][x + 1] + empty[y + 1][x] - empty[y][x] + u32::from(!filled);
+    }
+  }
+
+  let mut best = 0;
+  for (i, &a) in points.iter().enumerate() {
+    for &b in &points[i + 1..] {
+      let rect = Aabb::from_corners(a, b);
+      let [x0, x1] = [rect.min[0], rect.max[0]].map(|x| run_of(&columns, x));
+      let [y0, y1] = [rect.min[1], rect.max[1]].map(|y| run_of(&rows, y));
+      if empty[y1 + 1][x1 + 1] + empty[y0][x0]
+        == empty[y0][x1 + 1] + empty[y1 + 1][x0]
+      {
+        best = best.max(rect.area());
+      }
+    }
+  }
+  best
+}
+
+/// A rectilinear polygon made of `size` columns of random height, so that it
+/// never crosses itself. Unlike the real inputs, a column is often one tile
+/// wide, leaving a notch between its neighbours with no tile outside the loop.
+fn generate(rng: &mut Rng, size: usize) -> String {
+  let columns = size.max(1);
+  let span = 100 * (u64::try_from(columns).unwrap() + 1);
+  let mid = span / 2;
+
+  let mut xs = Vec::with_capacity(columns + 1);
+  let mut x = 0;
+  for _ in 0..=columns {
+    x += if rng.chance(1, 3) {
+      1
+    } else {
+      rng.range(2..=200)
+    };
+    xs.push(x);
+  }
+
+  // Tops stay above the middle and bottoms below it, so that neighbouring
+  // columns always overlap.
+  let mut sides = |range: RangeInclusive<u64>| {
+    let mut sides: Vec<u64> = Vec::with_capacity(columns);
+    while sides.len() < columns {
+      let y = rng.range(range.clone());
+      if sides.last() != Some(&y) {
+        sides.push(y);
+      }
+    }
+    sides
+  };
+  let tops = sides(mid + 1..=span);
+  let bottoms = sides(1..=mid - 1);
+
+  let mut points = Vec::with_capacity(4 * columns);
+  for i in 0..columns {
+    points.push([xs[i], tops[i]]);
+    points.push([xs[i + 1], tops[i]]);
+  }
+  for i in (0..columns).rev() {
+    points.push([xs[i + 1], bottoms[i]]);
+    points.push([xs[i], bottoms[i]]);
+  }
+
+  if rng.chance(1, 2) {
+    points.reverse();
+  }
+  let start = rng.range(0..=points.len() - 1);
+  points.rotate_left(start);
+  let transpose = rng.chance(1, 2);
+
+  let mut input = String::new();
+  for [x, y] in points {
+    let [x, y] = if transpose { [y, x] } else { [x, y] };
+    writeln!(input, "{x},{y}").unwrap();
+  }
+  input
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+  const DAY: u8 = 9;
+
+  type Input<'a> = Box<[Point]>;
+
+  fn parse<'a>(
+    input: &'a str,
+    _params: &Params,
+  ) -> Result<Self::Input<'a>, ParseError> {
+    parse_input(input)
+  }
+
+  fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+    generate(rng, size)
+  }
+
+  fn part1(input: &Self::Input<'_>) -> Answer {
+    part1(input).into()
+  }
+
+  fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+    Some(part2(input).into())
+  }
+
+  fn reference(input: &Self::Input<'_>, part: Part) -> Option<Answer> {
+    Some(match part {
+      Part::One => reference::part1(input).into(),
+      Part::Two => reference::part2(input).into(),
     })
-    .map(|(_, _, a)| a)
-    .unwrap()
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
-  const INPUT_TEST: &str = include_str!("input_test");
-  const INPUT: &str = include_str!("input");
-
   #[test]
-  fn part1_works() {
-    assert_eq!(part1(INPUT_TEST), 50);
-    assert_eq!(part1(INPUT), 4_777_824_480);
+  fn rejects_crossing_loops() {
+    let err = parse_input("0,2\n4,2\n4,4\n2,4\n2,0\n0,0\n").unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "day09 line 5 col 1: expected an edge that does not cross or touch \
+       the loop, found '2'"
+    );
+
+    // Doubling back along the previous edge touches it too.
+    assert!(parse_input("0,0\n4,0\n2,0\n2,2\n0,2\n").is_err());
+    assert!(parse_input("0,0\n2,0\n4,0\n4,2\n0,2\n").is_ok());
   }
 
   #[test]
-  fn part2_works() {
-    assert_eq!(part2(INPUT_TEST), 24);
-    assert_eq!(part2(INPUT), 1_542_119_040);
+  fn part2_fills_notches_one_tile_wide() {
+    // The notch between x = 2 and x = 3 has no tile outside the loop, so the
+    // whole floor is red or green.
+    let points =
+      parse_input("0,0\n5,0\n5,4\n3,4\n3,2\n2,2\n2,4\n0,4\n").unwrap();
+    assert_eq!(part2(&points), 30);
+    assert_eq!(reference::part2(&points), 30);
+
+    // One tile wider, and the notch is empty.
+    let points =
+      parse_input("0,0\n6,0\n6,4\n4,4\n4,2\n2,2\n2,4\n0,4\n").unwrap();
+    assert_eq!(part2(&points), reference::part2(&points));
+    assert_eq!(part2(&points), 15);
   }
 }
@@ -0,0 +1,69 @@
+//! Fills in every tile of the floor, so only suits small coordinates.
+
+use super::{Point, area};
+use crate::geom::{self, Aabb};
+
+/// Whether a tile lies on the loop or inside it, by counting the vertical
+/// edges to its right.
+fn is_filled(edges: &[Aabb<u64, 2>], tile: Point) -> bool {
+  if edges.iter().any(|edge| edge.contains(tile)) {
+    return true;
+  }
+  let crossings = edges
+    .iter()
+    .filter(|edge| {
+      edge.min[0] == edge.max[0]
+        && tile[0] < edge.min[0]
+        && edge.min[1] <= tile[1]
+        && tile[1] < edge.max[1]
+    })
+    .count();
+  crossings % 2 == 1
+}
+
+pub fn part1(points: &[Point]) -> u64 {
+  let mut best = 0;
+  for a in points {
+    for b in points {
+      best = best.max(area(a, b));
+    }
+  }
+  best
+}
+
+#[allow(clippy::cast_possible_truncation)]
+pub fn part2(points: &[Point]) -> u64 {
+  let edges: Vec<_> = geom::edges(points)
+    .map(|[a, b]| Aabb::from_corners(a, b))
+    .collect();
+  let min = |axis: usize| points.iter().map(|p| p[axis]).min().unwrap_or(0);
+  let max = |axis: usize| points.iter().map(|p| p[axis]).max().unwrap_or(0);
+  let origin = [min(0), min(1)];
+  let [width, height] =
+    [0, 1].map(|axis| (max(axis) - origin[axis]) as usize + 1);
+
+  // empty[y][x] counts the unfilled tiles above and to the left of (x, y).
+  let mut empty = vec![vec![0u64; width + 1]; height + 1];
+  for y in 0..height {
+    for x in 0..width {
+      let tile = Point::from([origin[0] + x as u64, origin[1] + y as u64]);
+      empty[y + 1][x + 1] = empty[y][x + 1] + empty[y + 1][x] - empty[y][x]
+        + u64::from(!is_filled(&edges, tile));
+    }
+  }
+
+  let mut best = 0;
+  for &a in points {
+    for &b in points {
+      let rect = Aabb::from_corners(a, b);
+      let [x0, y0] =
+        [0, 1].map(|axis| (rect.min[axis] - origin[axis]) as usize);
+      let [x1, y1] =
+        [0, 1].map(|axis| (rect.max[axis] - origin[axis]) as usize + 1);
+      if empty[y1][x1] + empty[y0][x0] == empty[y0][x1] + empty[y1][x0] {
+        best = best.max(rect.area());
+      }
+    }
+  }
+  best
+}
@@ -1,61 +1,131 @@
-use std::collections::{HashMap, VecDeque};
+mod reference;
+
+use std::{
+  collections::{HashMap, VecDeque},
+  fmt::Write,
+};
 
 use microlp::{ComparisonOp, OptimizationDirection, Problem, Variable};
 
+use crate::{
+  Answer, ParseError, Part, Solution, params::Params, parse::Cursor, rng::Rng,
+};
+
 #[derive(Debug)]
-struct Machine {
+pub struct Machine {
   lights: u16,
   buttons: Box<[u16]>,
   joltages: Box<[u16]>,
 }
 
-fn parse_input(input: &str) -> impl Iterator<Item = Machine> {
-  input.lines().map(|line| {
-    let line = line.strip_prefix('[').expect("Must start with ]");
-
-    let (lights, line) = line.split_once(']').expect("Must contain ]");
-
-    let lights = lights
-      .chars()
-      .enumerate()
-      .filter_map(|(i, c)| (c == '#').then_some(i))
-      .fold(0, |acc, i| acc | 1 << i);
-
-    let (buttons, line) = line.split_once('{').expect("Must contain {");
-
-    let buttons = buttons
-      .split_whitespace()
-      .map(|button| {
-        button
-          .strip_prefix('(')
-          .expect("Must start with (")
-          .strip_suffix(')')
-          .expect("Must end with )")
-          .split(',')
-          .map(|s| s.parse::<usize>().expect("Not an integer!"))
-          .fold(0, |acc, i| acc | 1 << i)
-      })
-      .collect();
+const MAX_LIGHTS: usize = u16::BITS as usize;
 
-    let joltages = line
-      .strip_suffix('}')
-      .expect("Must end with }")
-      .split(',')
-      .map(|s| s.parse().expect("Not an integer!"))
-      .collect();
+fn parse_index(
+  cursor: &mut Cursor,
+  light_count: usize,
+) -> Result<usize, ParseError> {
+  let before = *cursor;
+  let index = cursor.number()?;
+  if index < light_count {
+    Ok(index)
+  } else {
+    Err(before.error(format!("index below {light_count}")))
+  }
+}
 
-    Machine {
-      lights,
-      buttons,
-      joltages,
+/// Whether pressing some of `buttons` turns on exactly `lights`, by
+/// reducing the lights against a basis of the buttons over XOR.
+fn toggles_to(buttons: &[u16], mut lights: u16) -> bool {
+  let mut basis: Vec<u16> = Vec::new();
+  for &button in buttons {
+    let reduced = basis.iter().fold(button, |b, &v| b.min(b ^ v));
+    if reduced != 0 {
+      basis.push(reduced);
+      basis.sort_unstable_by(|a, b| b.cmp(a));
     }
-  })
+  }
+  for &vector in &basis {
+    lights = lights.min(lights ^ vector);
+  }
+  lights == 0
 }
 
-pub fn part1(input: &str) -> u16 {
-  let machines = parse_input(input);
+fn parse_input(input: &str) -> Result<Box<[Machine]>, ParseError> {
+  Cursor::new(Puzzle::DAY, input)
+    .lines()
+    .map(|mut line| {
+      let lights_start = line;
+      line.expect('[')?;
 
+      let mut lights = 0;
+      let mut i = 0;
+      loop {
+        match line.peek() {
+          Some(']') => break,
+          Some('#') if i < MAX_LIGHTS => lights |= 1 << i,
+          Some('.') if i < MAX_LIGHTS => {}
+          _ => return Err(line.error("'.', '#' or ']'")),
+        }
+        line.bump();
+        i += 1;
+      }
+      line.expect(']')?;
+
+      let mut buttons = Vec::new();
+      loop {
+        line.skip_whitespace();
+        if line.eat('{') {
+          break;
+        }
+        if !line.eat('(') {
+          return Err(line.error("'(' or '{'"));
+        }
+        let mut button = 0;
+        loop {
+          button |= 1 << parse_index(&mut line, i)?;
+          if !line.eat(',') {
+            break;
+          }
+        }
+        line.expect(')')?;
+        buttons.push(button);
+      }
+
+      let joltages_start = line;
+      let mut joltages = Vec::new();
+      loop {
+        joltages.push(line.number()?);
+        if !line.eat(',') {
+          break;
+        }
+      }
+      line.expect('}')?;
+      line.end()?;
+      if joltages.len() != i {
+        return Err(joltages_start.error(format!("{i} joltages")));
+      }
+      if !toggles_to(&buttons, lights) {
+        return Err(lights_start.error("lights that the buttons can toggle"));
+      }
+
+      let machine = Machine {
+        lights,
+        buttons: buttons.into(),
+        joltages: joltages.into(),
+      };
+      if fewest_presses(&machine).is_none() {
+        return Err(
+          joltages_start.error("joltages that the buttons can reach"),
+        );
+      }
+      Ok(machine)
+    })
+    .collect()
+}
+
+fn part1(machines: &[Machine]) -> u64 {
   machines
+    .iter()
     .map(|machine| {
       let mut best = HashMap::new();
       let mut queue = VecDeque::new();
@@ -91,61 +161,165 @@ pub fn part1(input: &str) -> u16 {
         }
       }
 
-      unreachable!()
+      unreachable!("parse_input checks that the lights can be reached")
     })
     .sum()
 }
 
-pub fn part2(input: &str) -> u16 {
-  let machines = parse_input(input);
+/// The fewest presses that reach the joltages, or `None` if no combination
+/// does.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn fewest_presses(machine: &Machine) -> Option<u64> {
+  let mut problem = Problem::new(OptimizationDirection::Minimize);
+
+  let vars: Box<[Variable]> = (0..machine.buttons.len())
+    .map(|_| problem.add_integer_var(1.0, (0, i32::MAX)))
+    .collect();
+
+  for (i, joltage) in machine.joltages.iter().enumerate() {
+    problem.add_constraint(
+      vars.iter().copied().zip(
+        machine
+          .buttons
+          .iter()
+          .map(|button| f64::from((button >> i) & 1)),
+      ),
+      ComparisonOp::Eq,
+      f64::from(*joltage),
+    );
+  }
+
+  let solution = problem.solve().ok()?;
+
+  Some(
+    vars
+      .into_iter()
+      .map(|var| solution.var_value_rounded(var) as u64)
+      .sum(),
+  )
+}
 
+fn part2(machines: &[Machine]) -> u64 {
   machines
+    .iter()
     .map(|machine| {
-      let mut problem = Problem::new(OptimizationDirection::Minimize);
+      fewest_presses(machine)
+        .expect("parse_input checks that the joltages can be reached")
+    })
+    .sum()
+}
 
-      let vars: Box<[Variable]> = (0..machine.buttons.len())
-        .map(|_| problem.add_integer_var(1.0, (0, i32::MAX)))
-        .collect();
+/// `size` machines of up to `size + 2` lights, and never more than ten, whose
+/// targets are reached by pressing a random combination of their buttons.
+fn generate(rng: &mut Rng, size: usize) -> String {
+  let mut input = String::new();
 
-      for (i, joltage) in machine.joltages.iter().enumerate() {
-        problem.add_constraint(
-          vars.iter().copied().zip(
-            machine
-              .buttons
-              .iter()
-              .map(|button| f64::from((button >> i) & 1)),
-          ),
-          ComparisonOp::Eq,
-          f64::from(*joltage),
-        );
+  for _ in 0..size.max(1) {
+    let light_count = rng.range(1..=(size + 2).min(10));
+    let buttons: Vec<u16> = (0..rng.range(1..=light_count + 2))
+      .map(|_| rng.range(1..=(1 << light_count) - 1))
+      .collect();
+
+    let mut lights = 0;
+    let mut joltages = vec![0; light_count];
+    for &button in &buttons {
+      if rng.chance(1, 2) {
+        lights ^= button;
       }
+      let presses = rng.range(0..=9u16);
+      for (i, joltage) in joltages.iter_mut().enumerate() {
+        if button >> i & 1 == 1 {
+          *joltage += presses;
+        }
+      }
+    }
+
+    input.push('[');
+    for i in 0..light_count {
+      input.push(if lights >> i & 1 == 1 { '#' } else { '.' });
+    }
+    input.push(']');
+    for button in buttons {
+      let indices: Vec<_> = (0..light_count)
+        .filter(|i| button >> i & 1 == 1)
+        .map(|i| i.to_string())
+        .collect();
+      write!(input, " ({})", indices.join(",")).unwrap();
+    }
+    let joltages: Vec<_> = joltages.iter().map(u16::to_string).collect();
+    writeln!(input, " {{{}}}", joltages.join(",")).unwrap();
+  }
 
-      let solution = problem.solve().unwrap();
+  input
+}
 
-      vars
-        .into_iter()
-        .map(|var| solution.var_value_rounded(var) as u16)
-        .sum::<u16>()
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+  const DAY: u8 = 10;
+
+  type Input<'a> = Box<[Machine]>;
+
+  fn parse<'a>(
+    input: &'a str,
+    _params: &Params,
+  ) -> Result<Self::Input<'a>, ParseError> {
+    parse_input(input)
+  }
+
+  fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+    generate(rng, size)
+  }
+
+  fn part1(input: &Self::Input<'_>) -> Answer {
+    part1(input).into()
+  }
+
+  fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+    Some(part2(input).into())
+  }
+
+  fn reference(input: &Self::Input<'_>, part: Part) -> Option<Answer> {
+    Some(match part {
+      Part::One => reference::part1(input).into(),
+      Part::Two => reference::part2(input).into(),
     })
-    .sum()
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
-  const INPUT_TEST: &str = include_str!("input_test");
-  const INPUT: &str = include_str!("input");
-
   #[test]
-  fn part1_works() {
-    assert_eq!(part1(INPUT_TEST), 7);
-    assert_eq!(part1(INPUT), 447);
+  fn rejects_unreachable_targets() {
+    let err = parse_input("[##] (0) {1,0}\n").unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "day10 line 1 col 1: expected lights that the buttons can toggle, \
+       found '['"
+    );
+    let err = parse_input("[#.] (0) {1,2}\n").unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "day10 line 1 col 11: expected joltages that the buttons can reach, \
+       found '1'"
+    );
+    // Every counter has a button, but the one button adds to both at once.
+    assert!(parse_input("[..] (0,1) {1,2}\n").is_err());
+    assert!(parse_input("[.##] (0,1) (0,2) {2,1,1}\n").is_ok());
   }
 
   #[test]
-  fn part2_works() {
-    assert_eq!(part2(INPUT_TEST), 33);
-    assert_eq!(part2(INPUT), 18_960);
+  fn part1_adds_past_u16() {
+    let machines: Vec<_> = (0..70_000)
+      .map(|_| Machine {
+        lights: 1,
+        buttons: [1].into(),
+        joltages: [1].into(),
+      })
+      .collect();
+    assert_eq!(part1(&machines), 70_000);
+    assert_eq!(reference::part1(&machines), 70_000);
   }
 }
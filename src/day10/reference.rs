@@ -0,0 +1,103 @@
+//! Searches button presses exhaustively, which is only quick for a handful of
+//! buttons.
+
+use super::Machine;
+
+fn fewest_toggles(machine: &Machine) -> u16 {
+  let buttons = &machine.buttons;
+  (0..1u32 << buttons.len())
+    .filter(|subset| {
+      let lights = (0..buttons.len())
+        .filter(|i| subset >> i & 1 == 1)
+        .fold(0, |lights, i| lights ^ buttons[i]);
+      lights == machine.lights
+    })
+    .map(|subset| u16::try_from(subset.count_ones()).unwrap())
+    .min()
+    .expect("no combination of presses reaches the lights")
+}
+
+/// Tries every press count for one button at a time, always picking a button
+/// on the counter that the fewest undecided buttons still reach, so that the
+/// last of them is forced to make up the rest.
+fn fewest_presses(
+  machine: &Machine,
+  decided: &mut [bool],
+  remaining: &mut [u16],
+  presses: u64,
+  best: &mut Option<u64>,
+) {
+  let bound = presses + u64::from(remaining.iter().copied().max().unwrap_or(0));
+  if best.is_some_and(|best| best <= bound) {
+    return;
+  }
+
+  let covers = |button: usize, i: usize| machine.buttons[button] >> i & 1 == 1;
+  let mut choice: Option<(usize, Vec<usize>)> = None;
+  for (i, &left) in remaining.iter().enumerate() {
+    let options: Vec<usize> = (0..decided.len())
+      .filter(|&button| !decided[button] && covers(button, i))
+      .collect();
+    if options.is_empty() {
+      if left != 0 {
+        return;
+      }
+    } else if choice
+      .as_ref()
+      .is_none_or(|(_, best)| options.len() < best.len())
+    {
+      choice = Some((i, options));
+    }
+  }
+
+  let Some((i, options)) = choice else {
+    *best = Some(presses);
+    return;
+  };
+
+  let button = options[0];
+  let counters: Vec<usize> = (0..remaining.len())
+    .filter(|&i| covers(button, i))
+    .collect();
+  let most = counters.iter().map(|&i| remaining[i]).min().unwrap_or(0);
+  let range = if options.len() == 1 {
+    remaining[i]..=remaining[i]
+  } else {
+    0..=most
+  };
+
+  decided[button] = true;
+  for count in range.take_while(|&count| count <= most) {
+    for &i in &counters {
+      remaining[i] -= count;
+    }
+    fewest_presses(
+      machine,
+      decided,
+      remaining,
+      presses + u64::from(count),
+      best,
+    );
+    for &i in &counters {
+      remaining[i] += count;
+    }
+  }
+  decided[button] = false;
+}
+
+pub fn part1(machines: &[Machine]) -> u64 {
+  machines.iter().map(fewest_toggles).map(u64::from).sum()
+}
+
+pub fn part2(machines: &[Machine]) -> u64 {
+  machines
+    .iter()
+    .map(|machine| {
+      let mut best = None;
+      let mut decided = vec![false; machine.buttons.len()];
+      let mut remaining = machine.joltages.to_vec();
+      fewest_presses(machine, &mut decided, &mut remaining, 0, &mut best);
+      best.expect("no combination of presses reaches the joltages")
+    })
+    .sum()
+}
@@ -1,89 +1,312 @@
-use std::{array, collections::HashMap};
+mod reference;
+
+use std::{
+  array,
+  collections::{BTreeSet, HashMap, HashSet},
+};
+
+use crate::{
+  Answer, ParseError, Part, Solution,
+  params::{Param, Params},
+  parse::Cursor,
+  rng::Rng,
+};
 
 type Node<'a> = &'a str;
+type Nodes<'a> = HashMap<Node<'a>, Box<[Node<'a>]>>;
 
-fn parse_input(
-  input: &str,
-) -> impl Iterator<Item = (Node<'_>, Box<[Node<'_>]>)> {
-  input.lines().map(|line| {
-    let (parent, children) = line.split_once(':').unwrap();
-    (parent, children.split_whitespace().collect())
-  })
+/// The devices the paths run between, named as in the puzzle by default.
+pub struct Route {
+  you: String,
+  svr: String,
+  fft: String,
+  dac: String,
+  out: String,
+}
+
+impl Route {
+  fn new(params: &Params) -> Self {
+    Self {
+      you: params.get("you"),
+      svr: params.get("svr"),
+      fft: params.get("fft"),
+      dac: params.get("dac"),
+      out: params.get("out"),
+    }
+  }
 }
 
+pub struct Devices<'a> {
+  nodes: Nodes<'a>,
+  route: Route,
+}
+
+fn parse_input(input: &str, route: Route) -> Result<Devices<'_>, ParseError> {
+  let mut parents = HashMap::new();
+  let lines = Cursor::new(Puzzle::DAY, input)
+    .lines()
+    .map(|mut line| {
+      let parent = line.until(':')?;
+      if parent.is_empty() {
+        return Err(parent.error("node name"));
+      }
+      parents.insert(parent.rest(), parent);
+      Ok((parent.rest(), line.split_whitespace().collect()))
+    })
+    .collect::<Result<HashMap<_, Vec<_>>, _>>()?;
+
+  if let Some((parent, child)) = find_cycle(&lines) {
+    return Err(
+      child.error(format!("a device that does not lead back to {parent}")),
+    );
+  }
+
+  let devices = Devices {
+    nodes: lines
+      .into_iter()
+      .map(|(parent, children)| {
+        (parent, children.iter().map(Cursor::rest).collect())
+      })
+      .collect(),
+    route,
+  };
+
+  // Counts only overflow at a device with outputs, which has a line.
+  if let Err(node) = part1_total(&devices).and(part2_total(&devices)) {
+    return Err(
+      parents[node].error("a device whose paths can be counted in a u64"),
+    );
+  }
+
+  Ok(devices)
+}
+
+/// Finds an output that closes a loop, along which there would be infinitely
+/// many paths, by walking depth first from every device in name order.
+fn find_cycle<'a>(
+  lines: &HashMap<Node<'a>, Vec<Cursor<'a>>>,
+) -> Option<(Node<'a>, Cursor<'a>)> {
+  let mut roots: Vec<Node> = lines.keys().copied().collect();
+  roots.sort_unstable();
+
+  let mut finished = HashSet::new();
+  let mut on_path = HashSet::new();
+  for root in roots {
+    if finished.contains(root) {
+      continue;
+    }
+    on_path.insert(root);
+    let mut stack = vec![(root, 0)];
+    while let Some(&(node, next)) = stack.last() {
+      let children = lines.get(node).map_or(&[][..], Vec::as_slice);
+      let Some(child) = children.get(next) else {
+        on_path.remove(node);
+        finished.insert(node);
+        stack.pop();
+        continue;
+      };
+      stack.last_mut().unwrap().1 += 1;
+
+      let name = child.rest();
+      if on_path.contains(name) {
+        return Some((node, *child));
+      }
+      if !finished.contains(name) {
+        on_path.insert(name);
+        stack.push((name, 0));
+      }
+    }
+  }
+
+  None
+}
+
+/// Counts the paths between a pair of devices, or returns the device at which
+/// the count overflows a `u64`.
 fn count_paths<'a>(
   cache: &mut HashMap<[Node<'a>; 2], u64>,
-  nodes: &HashMap<Node<'a>, Box<[Node<'a>]>>,
+  nodes: &Nodes<'a>,
   node_pair: [Node<'a>; 2],
-) -> u64 {
+) -> Result<u64, Node<'a>> {
   let [start_node, end_node] = node_pair;
 
   if start_node == end_node {
-    return 1;
+    return Ok(1);
   }
 
   if let Some(cached_count) = cache.get(&node_pair) {
-    return *cached_count;
+    return Ok(*cached_count);
   }
 
-  let mut count = 0;
+  let mut count = 0u64;
 
   if let Some(next_start_nodes) = nodes.get(start_node) {
     for next_start_node in next_start_nodes {
-      count += count_paths(cache, nodes, [next_start_node, end_node]);
+      count = count
+        .checked_add(count_paths(cache, nodes, [next_start_node, end_node])?)
+        .ok_or(start_node)?;
     }
   }
 
   cache.insert(node_pair, count);
 
-  count
+  Ok(count)
 }
 
+/// Multiplies the counts between each device on the route and the next,
+/// which is zero if any of them is, however large the others are.
 fn count_paths_through<'a>(
   cache: &mut HashMap<[Node<'a>; 2], u64>,
-  nodes: &HashMap<Node<'a>, Box<[Node<'a>]>>,
+  nodes: &Nodes<'a>,
   routes: &[Node<'a>],
-) -> u64 {
-  routes
+) -> Result<u64, Node<'a>> {
+  let counts: Vec<_> = routes
     .windows(2)
     .map(|pair| count_paths(cache, nodes, array::from_fn(|i| pair[i])))
-    .product()
+    .collect();
+  if counts.contains(&Ok(0)) {
+    return Ok(0);
+  }
+  counts.into_iter().try_fold(1u64, |product, count| {
+    product.checked_mul(count?).ok_or(routes[0])
+  })
 }
 
-pub fn part1(input: &str) -> u64 {
-  let nodes = parse_input(input).collect();
+fn part1_total<'a>(devices: &'a Devices<'a>) -> Result<u64, Node<'a>> {
+  let Route { you, out, .. } = &devices.route;
+  let mut cache = HashMap::new();
+
+  count_paths(&mut cache, &devices.nodes, [you, out].map(String::as_str))
+}
 
+fn part2_total<'a>(devices: &'a Devices<'a>) -> Result<u64, Node<'a>> {
+  let Route {
+    svr, fft, dac, out, ..
+  } = &devices.route;
+  let nodes = &devices.nodes;
   let mut cache = HashMap::new();
 
-  count_paths(&mut cache, &nodes, ["you", "out"])
+  count_paths_through(
+    &mut cache,
+    nodes,
+    &[svr, fft, dac, out].map(String::as_str),
+  )?
+  .checked_add(count_paths_through(
+    &mut cache,
+    nodes,
+    &[svr, dac, fft, out].map(String::as_str),
+  )?)
+  .ok_or(svr)
 }
 
-pub fn part2(input: &str) -> u64 {
-  let nodes = parse_input(input).collect();
+fn part1(devices: &Devices) -> u64 {
+  part1_total(devices).expect("parse_input checks for overflow")
+}
 
-  let mut cache = HashMap::new();
+fn part2(devices: &Devices) -> u64 {
+  part2_total(devices).expect("parse_input checks for overflow")
+}
+
+/// A DAG of `size` devices plus the named ones, with few enough paths that
+/// the reference can walk them one at a time.
+fn generate(rng: &mut Rng, size: usize) -> String {
+  const MAX_PATHS: u64 = 1 << 16;
+
+  let mut names = BTreeSet::new();
+  while names.len() < size {
+    let name: String = (0..3)
+      .map(|_| char::from(b'a' + rng.range(0..=25u8)))
+      .collect();
+    if !["you", "out", "svr", "fft", "dac"].contains(&name.as_str()) {
+      names.insert(name);
+    }
+  }
+
+  let mut order: Vec<String> = names.into_iter().collect();
+  rng.shuffle(&mut order);
+  for name in ["you", "fft", "dac"] {
+    let index = rng.range(0..=order.len());
+    order.insert(index, name.into());
+  }
+  order.insert(0, "svr".into());
+  order.push("out".into());
+
+  // Count the maximal paths from each device, treating every dead end as one,
+  // and fall back to a single edge to `out` whenever that grows too large.
+  let out = order.len() - 1;
+  let mut paths = vec![1u64; order.len()];
+  let mut lines = Vec::with_capacity(out);
+  for i in (0..out).rev() {
+    let mut children = Vec::new();
+    if !rng.chance(1, 10) {
+      for _ in 0..rng.range(1..=3usize) {
+        let child = rng.range(i + 1..=out);
+        if !children.contains(&child) {
+          children.push(child);
+        }
+      }
+    }
 
-  count_paths_through(&mut cache, &nodes, &["svr", "fft", "dac", "out"])
-    + count_paths_through(&mut cache, &nodes, &["svr", "dac", "fft", "out"])
+    let count = children
+      .iter()
+      .try_fold(0u64, |count, &child| count.checked_add(paths[child]))
+      .filter(|&count| count <= MAX_PATHS);
+    match count {
+      Some(0) => {}
+      Some(count) => paths[i] = count,
+      None => children = vec![out],
+    }
+
+    let mut line = format!("{}:", order[i]);
+    for child in children {
+      line.push(' ');
+      line.push_str(&order[child]);
+    }
+    lines.push(line);
+  }
+
+  rng.shuffle(&mut lines);
+  lines.join("\n") + "\n"
 }
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+  const DAY: u8 = 11;
+
+  type Input<'a> = Devices<'a>;
 
-  const INPUT_TEST_0: &str = include_str!("input_test_0");
-  const INPUT_TEST_1: &str = include_str!("input_test_1");
-  const INPUT: &str = include_str!("input");
+  const PARAMS: &'static [Param] = &[
+    Param::name("you", "you"),
+    Param::name("svr", "svr"),
+    Param::name("fft", "fft"),
+    Param::name("dac", "dac"),
+    Param::name("out", "out"),
+  ];
+
+  fn parse<'a>(
+    input: &'a str,
+    params: &Params,
+  ) -> Result<Self::Input<'a>, ParseError> {
+    parse_input(input, Route::new(params))
+  }
+
+  fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+    generate(rng, size)
+  }
+
+  fn part1(input: &Self::Input<'_>) -> Answer {
+    part1(input).into()
+  }
 
-  #[test]
-  fn part1_works() {
-    assert_eq!(part1(INPUT_TEST_0), 5);
-    assert_eq!(part1(INPUT), 428);
+  fn part2(input: &Self::Input<'_>) -> Option<Answer> {
+    Some(part2(input).into())
   }
 
-  #[test]
-  fn part2_works() {
-    assert_eq!(part2(INPUT_TEST_1), 2);
-    assert_eq!(part2(INPUT), 331_468_292_364_745);
+  fn reference(input: &Self::Input<'_>, part: Part) -> Option<Answer> {
+    Some(match part {
+      Part::One => reference::part1(input).into(),
+      Part::Two => reference::part2(input).into(),
+    })
   }
 }
@@ -0,0 +1,44 @@
+//! Walks every path one at a time.
+
+use super::{Devices, Nodes, Route};
+
+/// Calls `visit` with every path from `node` to `out`.
+fn walk<'a>(
+  nodes: &Nodes<'a>,
+  node: &'a str,
+  out: &str,
+  path: &mut Vec<&'a str>,
+  visit: &mut impl FnMut(&[&'a str]),
+) {
+  path.push(node);
+  if node == out {
+    visit(path);
+  } else if let Some(children) = nodes.get(node) {
+    for child in children {
+      walk(nodes, child, out, path, visit);
+    }
+  }
+  path.pop();
+}
+
+pub fn part1(devices: &Devices) -> u64 {
+  let Route { you, out, .. } = &devices.route;
+  let mut count = 0;
+  walk(&devices.nodes, you, out, &mut Vec::new(), &mut |_| {
+    count += 1;
+  });
+  count
+}
+
+pub fn part2(devices: &Devices) -> u64 {
+  let Route {
+    svr, fft, dac, out, ..
+  } = &devices.route;
+  let mut count = 0;
+  walk(&devices.nodes, svr, out, &mut Vec::new(), &mut |path| {
+    if path.contains(&fft.as_str()) && path.contains(&dac.as_str()) {
+      count += 1;
+    }
+  });
+  count
+}
@@ -1,18 +1,29 @@
-use std::array;
+mod reference;
+
+use std::fmt::Write;
+
+use crate::{
+  Answer, ParseError, Part, Solution,
+  params::Params,
+  parse::{Cursor, Lines},
+  rng::Rng,
+};
 
 const PRESENT_COUNT: usize = 6;
 
 /** A 3x3 area */
-struct Present([u8; 3]);
+#[derive(Default)]
+pub struct Present([u8; 3]);
 
 impl Present {
+  #[allow(clippy::cast_possible_truncation)]
   fn area(&self) -> u16 {
     self.0.iter().map(|val| val.count_ones() as u16).sum()
   }
 }
 
 #[derive(Debug)]
-struct Region {
+pub struct Region {
   dimensions: [u8; 2],
   counts: [u8; PRESENT_COUNT],
 }
@@ -26,44 +37,67 @@ impl Region {
 type Presents = [Present; PRESENT_COUNT];
 type Regions = Box<[Region]>;
 
-fn parse_input(input: &str) -> (Presents, Regions) {
-  let mut lines = input.lines();
-
-  let presents = array::from_fn(|_| {
-    lines.next().unwrap();
-    let bytes = array::from_fn(|_| {
-      lines
-        .next()
-        .unwrap()
-        .chars()
-        .fold(0, |acc, c| acc << 1 | u8::from(c == '#'))
-    });
-    lines.next().unwrap();
-    Present(bytes)
-  });
-
-  let regions = lines
-    .map(|line| {
-      let (size, line) = line.split_once(':').unwrap();
-
-      let mut dimensions_iter = size.split('x').map(|x| x.parse().unwrap());
-      let dimensions = array::from_fn(|_| dimensions_iter.next().unwrap());
-
-      let mut counts_iter = line.split_whitespace().map(|x| x.parse().unwrap());
-      let counts = array::from_fn(|_| counts_iter.next().unwrap());
-
-      Region { dimensions, counts }
-    })
-    .collect();
+fn parse_present(lines: &mut Lines) -> Result<Present, ParseError> {
+  let mut header = lines.expect("present index")?;
+  header.number::<u8>()?;
+  header.expect(':')?;
+  header.end()?;
+
+  let mut bytes = [0; 3];
+  for byte in &mut bytes {
+    let mut line = lines.expect("present shape")?;
+    for _ in 0..3 {
+      let bit = match line.peek() {
+        Some('#') => 1,
+        Some('.') => 0,
+        _ => return Err(line.error("'#' or '.'")),
+      };
+      *byte = *byte << 1 | bit;
+      line.bump();
+    }
+    line.end()?;
+  }
+
+  lines.expect("blank line")?.end()?;
+
+  Ok(Present(bytes))
+}
+
+fn parse_region(mut line: Cursor) -> Result<Region, ParseError> {
+  let width = line.number()?;
+  line.expect('x')?;
+  let height = line.number()?;
+  line.expect(':')?;
+
+  let mut counts = [0; PRESENT_COUNT];
+  for count in &mut counts {
+    line.expect(' ')?;
+    *count = line.number()?;
+  }
+  line.end()?;
 
-  (presents, regions)
+  Ok(Region {
+    dimensions: [width, height],
+    counts,
+  })
 }
 
-pub fn part1(input: &str) -> usize {
-  let (presents, regions) = parse_input(input);
+fn parse_input(input: &str) -> Result<(Presents, Regions), ParseError> {
+  let mut lines = Cursor::new(Puzzle::DAY, input).lines();
+
+  let mut presents = Presents::default();
+  for present in &mut presents {
+    *present = parse_present(&mut lines)?;
+  }
 
+  let regions = lines.map(parse_region).collect::<Result<_, _>>()?;
+
+  Ok((presents, regions))
+}
+
+fn part1((presents, regions): &(Presents, Regions)) -> usize {
   regions
-    .into_iter()
+    .iter()
     .filter(|region| {
       let total_present_area: u16 = (0..PRESENT_COUNT)
         .map(|i| u16::from(region.counts[i]) * presents[i].area())
@@ -75,14 +109,115 @@ pub fn part1(input: &str) -> usize {
     .count()
 }
 
+/// Six presents of at least five cells, then `size` regions that either fit
+/// one present per 3x3 block or are smaller than the presents' total area,
+/// like the real inputs.
+fn generate(rng: &mut Rng, size: usize) -> String {
+  let mut input = String::new();
+
+  let mut areas = [0; PRESENT_COUNT];
+  for (i, area) in areas.iter_mut().enumerate() {
+    let mut bits = 0u16;
+    while bits.count_ones() < 5 {
+      bits |= 1 << rng.range(0..=8u8);
+      if rng.chance(1, 2) {
+        bits |= 1 << rng.range(0..=8u8);
+      }
+    }
+    *area = bits.count_ones();
+
+    writeln!(input, "{i}:").unwrap();
+    for row in 0..3 {
+      for column in 0..3 {
+        input.push(if bits >> (3 * row + column) & 1 == 1 {
+          '#'
+        } else {
+          '.'
+        });
+      }
+      input.push('\n');
+    }
+    input.push('\n');
+  }
+
+  let max_side = (6 + 4 * size).min(50);
+  for _ in 0..size.max(1) {
+    let width = rng.range(3..=max_side);
+    let height = rng.range(3..=max_side);
+    let mut counts = [0u8; PRESENT_COUNT];
+
+    if rng.chance(1, 2) {
+      let blocks = (width / 3) * (height / 3);
+      for _ in 0..rng.range(0..=blocks) {
+        counts[rng.range(0..=PRESENT_COUNT - 1)] += 1;
+      }
+    } else {
+      let mut area = 0;
+      while area <= width * height {
+        let i = rng.range(0..=PRESENT_COUNT - 1);
+        counts[i] += 1;
+        area += areas[i] as usize;
+      }
+    }
+
+    write!(input, "{width}x{height}:").unwrap();
+    for count in counts {
+      write!(input, " {count}").unwrap();
+    }
+    input.push('\n');
+  }
+
+  input
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+  const DAY: u8 = 12;
+
+  type Input<'a> = (Presents, Regions);
+
+  const PARTS: &'static [Part] = &[Part::One];
+
+  fn parse<'a>(
+    input: &'a str,
+    _params: &Params,
+  ) -> Result<Self::Input<'a>, ParseError> {
+    parse_input(input)
+  }
+
+  fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+    generate(rng, size)
+  }
+
+  fn part1(input: &Self::Input<'_>) -> Answer {
+    part1(input).into()
+  }
+
+  fn reference(input: &Self::Input<'_>, part: Part) -> Option<Answer> {
+    (part == Part::One).then(|| reference::part1(input).into())
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
-  const INPUT: &str = include_str!("input");
+  /// Six copies of one present, and the given regions.
+  fn alike(present: &str, regions: &str) -> (Presents, Regions) {
+    let mut input = String::new();
+    for i in 0..PRESENT_COUNT {
+      writeln!(input, "{i}:\n{present}").unwrap();
+    }
+    parse_input(&(input + regions)).unwrap()
+  }
 
   #[test]
-  fn part1_works() {
-    assert_eq!(part1(INPUT), 460);
+  fn part1_trusts_the_area() {
+    // Two crosses leave two cells of a 4x3 region spare, but only packing
+    // them shows that they cannot fit, which the real inputs never need.
+    let input = alike(".#.\n###\n.#.\n", "4x3: 2 0 0 0 0 0\n");
+    assert_eq!(part1(&input), 1);
+    assert_eq!(reference::part1(&input), 0);
   }
 }
@@ -0,0 +1,90 @@
+//! Actually packs the presents, trying every placement and orientation.
+
+use super::{PRESENT_COUNT, Present, Presents, Region, Regions};
+
+type Shape = Vec<(usize, usize)>;
+
+/// The cells of a present in each of its distinct orientations.
+fn orientations(present: &Present) -> Vec<Shape> {
+  let mut shapes: Vec<Shape> = Vec::new();
+  let mut cells: Shape = (0..3)
+    .flat_map(|y| (0..3).map(move |x| (x, y)))
+    .filter(|&(x, y)| present.0[y] >> (2 - x) & 1 == 1)
+    .collect();
+  for flip in 0..2 {
+    for _ in 0..4 {
+      cells = cells.iter().map(|&(x, y)| (2 - y, x)).collect();
+      let min_x = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+      let min_y = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+      let mut shape: Shape =
+        cells.iter().map(|&(x, y)| (x - min_x, y - min_y)).collect();
+      shape.sort_unstable();
+      if !shapes.contains(&shape) {
+        shapes.push(shape);
+      }
+    }
+    if flip == 0 {
+      cells = cells.iter().map(|&(x, y)| (2 - x, y)).collect();
+    }
+  }
+  shapes
+}
+
+/// Places the presents still to go, each after the previous one in reading
+/// order when they are the same kind.
+fn pack(
+  shapes: &[Vec<Shape>],
+  filled: &mut Vec<Vec<bool>>,
+  pending: &[usize],
+  start: usize,
+  free: usize,
+) -> bool {
+  let Some((&kind, rest)) = pending.split_first() else {
+    return true;
+  };
+  let needed: usize = pending.iter().map(|&kind| shapes[kind][0].len()).sum();
+  if free < needed {
+    return false;
+  }
+
+  let (width, height) = (filled.first().map_or(0, Vec::len), filled.len());
+  for at in start..width * height {
+    let (x0, y0) = (at % width, at / width);
+    for shape in &shapes[kind] {
+      let fits = shape.iter().all(|&(x, y)| {
+        x0 + x < width && y0 + y < height && !filled[y0 + y][x0 + x]
+      });
+      if !fits {
+        continue;
+      }
+      for &(x, y) in shape {
+        filled[y0 + y][x0 + x] = true;
+      }
+      let next = if rest.first() == Some(&kind) { at } else { 0 };
+      if pack(shapes, filled, rest, next, free - shape.len()) {
+        return true;
+      }
+      for &(x, y) in shape {
+        filled[y0 + y][x0 + x] = false;
+      }
+    }
+  }
+  false
+}
+
+fn fits(presents: &Presents, region: &Region) -> bool {
+  let shapes: Vec<Vec<Shape>> = presents.iter().map(orientations).collect();
+  let pending: Vec<usize> = (0..PRESENT_COUNT)
+    .flat_map(|kind| (0..region.counts[kind]).map(move |_| kind))
+    .collect();
+  let [width, height] = region.dimensions.map(usize::from);
+  let mut filled = vec![vec![false; width]; height];
+  pack(&shapes, &mut filled, &pending, 0, width * height)
+}
+
+pub fn part1((presents, regions): &(Presents, Regions)) -> usize {
+  regions
+    .iter()
+    .filter(|region| fits(presents, region))
+    .count()
+}
@@ -0,0 +1,212 @@
+use std::{
+  fmt,
+  panic::{self, AssertUnwindSafe},
+};
+
+use crate::{
+  Answer, Day, Part, params::Params, rng::Rng, sandbox::panic_message,
+};
+
+/// What a solver made of an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+  Answer(Answer),
+  Panicked(String),
+}
+
+impl Outcome {
+  /// Runs a solver, or returns `None` if it has nothing to say about the part.
+  fn of(solve: impl FnOnce() -> Option<Answer>) -> Option<Self> {
+    match panic::catch_unwind(AssertUnwindSafe(solve)) {
+      Ok(answer) => answer.map(Outcome::Answer),
+      Err(payload) => Some(Outcome::Panicked(panic_message(&*payload))),
+    }
+  }
+
+  /// Whether two outcomes agree, counting any two panics as the same.
+  fn agrees(&self, other: &Self) -> bool {
+    match (self, other) {
+      (Outcome::Answer(a), Outcome::Answer(b)) => a == b,
+      (Outcome::Panicked(_), Outcome::Panicked(_)) => true,
+      _ => false,
+    }
+  }
+}
+
+impl fmt::Display for Outcome {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Outcome::Answer(answer) => answer.fmt(f),
+      Outcome::Panicked(message) => write!(f, "a panic ({message})"),
+    }
+  }
+}
+
+/// A generated input on which the fast solution and the reference disagree.
+#[derive(Debug, Clone)]
+pub struct Mismatch {
+  pub day: u8,
+  pub part: Part,
+  pub seed: u64,
+  pub size: usize,
+  /// The parameters the input was generated for and parsed with.
+  pub params: Params,
+  pub reference: Outcome,
+  pub fast: Outcome,
+  /// The input, shrunk as far as it will go while still disagreeing.
+  pub input: String,
+}
+
+impl fmt::Display for Mismatch {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "day{:02} part {} (seed {}, size {}",
+      self.day, self.part, self.seed, self.size
+    )?;
+    if self.params.overrides().next().is_some() {
+      write!(f, ", {}", self.params)?;
+    }
+    writeln!(f, "): expected {}, found {}", self.reference, self.fast)?;
+    f.write_str(&self.input)
+  }
+}
+
+/// Runs both solvers on an input, returning their outcomes if they disagree.
+/// Inputs that do not parse, and parts without a reference, never disagree.
+pub fn compare(
+  day: &Day,
+  part: Part,
+  input: &str,
+  params: &Params,
+) -> Option<[Outcome; 2]> {
+  let parsed = day.parse(input, params).ok()?;
+  let reference = Outcome::of(|| parsed.reference(part))?;
+  let fast = Outcome::of(|| parsed.solve(part))?;
+  (!reference.agrees(&fast)).then_some([reference, fast])
+}
+
+/// Which of the two outcomes are panics, so that shrinking does not trade a
+/// wrong answer for an unrelated crash.
+fn panics(outcomes: &[Outcome; 2]) -> [bool; 2] {
+  outcomes
+    .each_ref()
+    .map(|outcome| matches!(outcome, Outcome::Panicked(_)))
+}
+
+/// Deletes runs of lines from a disagreeing input, halving the run length
+/// down to single lines, for as long as the result still disagrees in the
+/// same way.
+pub fn shrink(day: &Day, part: Part, input: &str, params: &Params) -> String {
+  let Some(outcomes) = compare(day, part, input, params) else {
+    return input.to_string();
+  };
+  let still_fails = |lines: &[&str]| {
+    compare(day, part, &join(lines), params)
+      .is_some_and(|candidate| panics(&candidate) == panics(&outcomes))
+  };
+
+  let mut lines: Vec<&str> = input.lines().collect();
+  let mut run = lines.len().div_ceil(2);
+
+  while run != 0 {
+    let mut start = 0;
+    while start < lines.len() {
+      let end = (start + run).min(lines.len());
+      let candidate: Vec<&str> = [&lines[..start], &lines[end..]].concat();
+      if still_fails(&candidate) {
+        lines = candidate;
+      } else {
+        start = end;
+      }
+    }
+    run /= 2;
+  }
+
+  join(&lines)
+}
+
+fn join(lines: &[&str]) -> String {
+  lines.iter().flat_map(|line| [line, "\n"]).collect()
+}
+
+/// Checks both parts of one generated input, and the parameters it was
+/// generated for, shrinking the first disagreement found.
+pub fn check(day: &Day, seed: u64, size: usize) -> Result<(), Box<Mismatch>> {
+  let mut rng = Rng::new(seed);
+  let params = day.generate_params(&mut rng);
+  let input = day.generate(&mut rng, size, &params);
+
+  for part in Part::ALL {
+    if compare(day, part, &input, &params).is_some() {
+      let input = shrink(day, part, &input, &params);
+      let [reference, fast] = compare(day, part, &input, &params)
+        .expect("shrinking keeps the mismatch");
+      return Err(Box::new(Mismatch {
+        day: day.number,
+        part,
+        seed,
+        size,
+        params,
+        reference,
+        fast,
+        input,
+      }));
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fmt::Write;
+
+  use super::*;
+  use crate::{ParseError, Solution, params::Params};
+
+  /// Sums its lines, except that the fast part forgets any line of 7.
+  struct Buggy;
+
+  impl Solution for Buggy {
+    const DAY: u8 = 0;
+
+    type Input<'a> = Vec<u64>;
+
+    fn parse<'a>(
+      input: &'a str,
+      _params: &Params,
+    ) -> Result<Self::Input<'a>, ParseError> {
+      Ok(input.lines().filter_map(|line| line.parse().ok()).collect())
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+      let mut input = String::new();
+      for _ in 0..size {
+        writeln!(input, "{}", rng.range(0..=9u64)).unwrap();
+      }
+      input
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+      input.iter().filter(|&&n| n != 7).sum::<u64>().into()
+    }
+
+    fn reference(input: &Self::Input<'_>, part: Part) -> Option<Answer> {
+      (part == Part::One).then(|| input.iter().sum::<u64>().into())
+    }
+  }
+
+  #[test]
+  fn shrinks_mismatches() {
+    let day = Day::of::<Buggy>();
+    let mismatch = (0..100)
+      .find_map(|seed| check(&day, seed, 20).err())
+      .unwrap();
+    assert_eq!(mismatch.part, Part::One);
+    assert_eq!(mismatch.input, "7\n");
+    assert_eq!(mismatch.reference, Outcome::Answer(Answer::U64(7)));
+    assert_eq!(mismatch.fast, Outcome::Answer(Answer::U64(0)));
+    assert!(check(&day, 0, 0).is_ok());
+  }
+}
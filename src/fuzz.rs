@@ -0,0 +1,325 @@
+use std::{
+  fmt::Write,
+  fs, io,
+  path::{Path, PathBuf},
+  time::Duration,
+};
+
+use crate::{
+  Day, Part,
+  params::{self, Params},
+  rng::Rng,
+  sandbox::{self, Failure, Limits},
+};
+
+/// A way of corrupting an input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mutation {
+  /// Flips one bit of a random byte.
+  FlipByte,
+  /// Cuts the input off at a random byte.
+  Truncate,
+  /// Repeats a random line right after itself.
+  DuplicateLine,
+  /// Appends digits to a random number, to probe for overflows.
+  InflateNumber,
+}
+
+impl Mutation {
+  pub const ALL: [Mutation; 4] = [
+    Mutation::FlipByte,
+    Mutation::Truncate,
+    Mutation::DuplicateLine,
+    Mutation::InflateNumber,
+  ];
+
+  pub fn apply(self, rng: &mut Rng, bytes: &mut Vec<u8>) {
+    if bytes.is_empty() {
+      return;
+    }
+    let last = bytes.len() - 1;
+
+    match self {
+      Mutation::FlipByte => {
+        bytes[rng.range(0..=last)] ^= 1 << rng.range(0..=7u8);
+      }
+      Mutation::Truncate => bytes.truncate(rng.range(0..=last)),
+      Mutation::DuplicateLine => {
+        let starts: Vec<usize> = (0..=last)
+          .filter(|&i| i == 0 || bytes[i - 1] == b'\n')
+          .collect();
+        let start = *rng.choose(&starts);
+        let end = bytes[start..]
+          .iter()
+          .position(|&byte| byte == b'\n')
+          .map_or(bytes.len(), |i| start + i + 1);
+        let line = bytes[start..end].to_vec();
+        bytes.splice(end..end, line);
+      }
+      Mutation::InflateNumber => {
+        let ends: Vec<usize> = (0..=last)
+          .filter(|&i| {
+            bytes[i].is_ascii_digit()
+              && bytes.get(i + 1).is_none_or(|byte| !byte.is_ascii_digit())
+          })
+          .map(|i| i + 1)
+          .collect();
+        if ends.is_empty() {
+          return;
+        }
+        let end = *rng.choose(&ends);
+        let digits: Vec<u8> = (0..rng.range(1..=20usize))
+          .map(|_| b'0' + rng.range(0..=9u8))
+          .collect();
+        bytes.splice(end..end, digits);
+      }
+    }
+  }
+}
+
+/// Applies one to four random mutations to an input.
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+  let mut bytes = input.as_bytes().to_vec();
+  for _ in 0..rng.range(1..=4usize) {
+    let mutation = *rng.choose(&Mutation::ALL);
+    mutation.apply(rng, &mut bytes);
+  }
+  String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Parses an input with `params` and, if that succeeds, solves both parts of
+/// it, within `limit`.
+pub fn run(
+  day: &Day,
+  input: &str,
+  params: &Params,
+  limit: Duration,
+) -> Result<(), Failure> {
+  let day = *day;
+  let input = input.to_string();
+  let params = params.clone();
+  let limits = Limits {
+    deadline: limit,
+    ..Limits::default()
+  };
+
+  sandbox::run(format!("fuzz day{:02}", day.number), limits, move || {
+    if let Ok(parsed) = day.parse(&input, &params) {
+      for part in Part::ALL {
+        parsed.solve(part);
+      }
+    }
+  })
+}
+
+/// FNV-1a, to name corpus files after their contents.
+fn fingerprint(input: &str) -> u64 {
+  input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+    (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+  })
+}
+
+/// Inputs that once crashed a day, one file each in a directory per day, so
+/// that they can be replayed as regression tests. An input that needs other
+/// parameters than the defaults has them next to it, one `name=value` per
+/// line of a `.params` file.
+#[derive(Debug, Clone)]
+pub struct Corpus {
+  dir: PathBuf,
+}
+
+impl Corpus {
+  pub fn new(dir: impl Into<PathBuf>) -> Self {
+    Self { dir: dir.into() }
+  }
+
+  pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+      .join("fuzz")
+      .join("corpus")
+  }
+
+  pub fn dir(&self) -> &Path {
+    &self.dir
+  }
+
+  /// Saves an input and its parameters, named after them so that the same
+  /// crash is only kept once.
+  pub fn record(
+    &self,
+    day: u8,
+    input: &str,
+    params: &Params,
+  ) -> io::Result<PathBuf> {
+    let dir = self.dir.join(format!("day{day:02}"));
+    fs::create_dir_all(&dir)?;
+
+    let overrides =
+      params
+        .overrides()
+        .fold(String::new(), |mut overrides, (name, value)| {
+          writeln!(overrides, "{name}={value}").unwrap();
+          overrides
+        });
+    let hash = if overrides.is_empty() {
+      fingerprint(input)
+    } else {
+      fingerprint(&(overrides.clone() + input))
+    };
+    let path = dir.join(format!("{hash:016x}"));
+    fs::write(&path, input)?;
+    if !overrides.is_empty() {
+      fs::write(path.with_extension("params"), overrides)?;
+    }
+    Ok(path)
+  }
+
+  /// Every input saved for a day, with its parameters, in name order.
+  pub fn cases(&self, day: &Day) -> io::Result<Vec<(PathBuf, String, Params)>> {
+    let dir = self.dir.join(format!("day{:02}", day.number));
+    let entries = match fs::read_dir(dir) {
+      Ok(entries) => entries,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+      Err(err) => return Err(err),
+    };
+
+    let mut paths = entries
+      .map(|entry| Ok(entry?.path()))
+      .collect::<io::Result<Vec<_>>>()?;
+    paths.retain(|path| path.extension().is_none_or(|ext| ext != "params"));
+    paths.sort();
+
+    paths
+      .into_iter()
+      .map(|path| {
+        let input = fs::read_to_string(&path)?;
+        let params = match fs::read_to_string(path.with_extension("params")) {
+          Ok(overrides) => overrides
+            .lines()
+            .map(params::split_override)
+            .collect::<Result<Vec<_>, _>>()
+            .and_then(|overrides| day.resolve(overrides))
+            .map_err(|err| {
+              io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {err}", path.display()),
+              )
+            })?,
+          Err(err) if err.kind() == io::ErrorKind::NotFound => day.defaults(),
+          Err(err) => return Err(err),
+        };
+        Ok((path, input, params))
+      })
+      .collect()
+  }
+}
+
+/// A mutated input that a day failed on.
+#[derive(Debug, Clone)]
+pub struct Crash {
+  pub day: u8,
+  pub failure: Failure,
+  pub input: String,
+  pub params: Params,
+}
+
+/// Runs a day on `runs` mutations of its seed inputs, each parsed with the
+/// parameters it came with, returning the crashes.
+pub fn fuzz(
+  day: &Day,
+  seeds: &[(String, Params)],
+  rng: &mut Rng,
+  runs: usize,
+  limit: Duration,
+) -> Vec<Crash> {
+  let mut crashes = Vec::new();
+  if seeds.is_empty() {
+    return crashes;
+  }
+
+  for _ in 0..runs {
+    let (seed, params) = rng.choose(seeds);
+    let input = mutate(rng, seed);
+    if let Err(failure) = run(day, &input, params, limit) {
+      crashes.push(Crash {
+        day: day.number,
+        failure,
+        input,
+        params: params.clone(),
+      });
+    }
+  }
+
+  crashes
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn mutates_inputs() {
+    const INPUT: &[u8] = b"ab 12\ncd 7\n";
+    let mut rng = Rng::new(3);
+    let apply = |mutation: Mutation, rng: &mut Rng| {
+      let mut bytes = INPUT.to_vec();
+      mutation.apply(rng, &mut bytes);
+      bytes
+    };
+
+    let flipped = apply(Mutation::FlipByte, &mut rng);
+    let diffs: Vec<u8> =
+      flipped.iter().zip(INPUT).map(|(a, b)| a ^ b).collect();
+    assert_eq!(diffs.iter().map(|diff| diff.count_ones()).sum::<u32>(), 1);
+    assert!(INPUT.starts_with(&apply(Mutation::Truncate, &mut rng)));
+    assert!(
+      [&b"ab 12\nab 12\ncd 7\n"[..], b"ab 12\ncd 7\ncd 7\n"]
+        .contains(&apply(Mutation::DuplicateLine, &mut rng).as_slice())
+    );
+    let inflated = apply(Mutation::InflateNumber, &mut rng);
+    assert!(inflated.len() > INPUT.len());
+    assert!(inflated.iter().filter(|byte| byte.is_ascii_digit()).count() > 3);
+
+    let mut empty = Vec::new();
+    for mutation in Mutation::ALL {
+      mutation.apply(&mut rng, &mut empty);
+    }
+    assert!(empty.is_empty());
+  }
+
+  #[test]
+  fn reports_failures() {
+    let limit = Duration::from_secs(5);
+    let day = crate::day(1).unwrap();
+    let params = day.defaults();
+    assert_eq!(run(day, "L1\nR2\n", &params, limit), Ok(()));
+    assert_eq!(run(day, "garbage", &params, limit), Ok(()));
+  }
+
+  #[test]
+  fn records_cases() {
+    let dir =
+      std::env::temp_dir().join(format!("aoc-corpus-{}", std::process::id()));
+    let corpus = Corpus::new(&dir);
+    let day = crate::day(1).unwrap();
+    assert!(corpus.cases(day).unwrap().is_empty());
+
+    let defaults = day.defaults();
+    let path = corpus.record(1, "L1\n", &defaults).unwrap();
+    assert_eq!(corpus.record(1, "L1\n", &defaults).unwrap(), path);
+    corpus.record(1, "R2\n", &defaults).unwrap();
+    let start = day.resolve([("start", "3")]).unwrap();
+    assert_ne!(corpus.record(1, "L1\n", &start).unwrap(), path);
+    let cases: Vec<_> = corpus
+      .cases(day)
+      .unwrap()
+      .into_iter()
+      .map(|(_, input, params)| (input, params))
+      .collect();
+    assert_eq!(cases.len(), 3);
+    assert!(cases.contains(&("R2\n".to_string(), defaults)));
+    assert!(cases.contains(&("L1\n".to_string(), start)));
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+}
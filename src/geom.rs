@@ -0,0 +1,249 @@
+use std::{
+  array,
+  iter::{Product, Sum},
+  ops::{Add, Index, IndexMut, Mul},
+  str::FromStr,
+};
+
+use crate::{ParseError, parse::Cursor};
+
+/// An unsigned magnitude, such as a distance between two coordinates.
+pub trait Magnitude:
+  Copy + Ord + Default + Add<Output = Self> + Mul<Output = Self> + Sum + Product
+{
+  const ONE: Self;
+}
+
+/// An integer coordinate, signed or unsigned.
+pub trait Coord: Copy + Ord + Default + FromStr {
+  type Magnitude: Magnitude;
+
+  /// Whether the coordinate can be negative, and so parses a minus sign.
+  const SIGNED: bool;
+
+  fn abs_diff(self, other: Self) -> Self::Magnitude;
+}
+
+macro_rules! impl_coord {
+  ($($ty:ty => $magnitude:ty),*) => {
+    $(
+      impl Coord for $ty {
+        type Magnitude = $magnitude;
+
+        const SIGNED: bool = <$ty>::MIN != 0;
+
+        fn abs_diff(self, other: Self) -> $magnitude {
+          <$ty>::abs_diff(self, other)
+        }
+      }
+    )*
+  };
+}
+
+macro_rules! impl_magnitude {
+  ($($ty:ty),*) => {
+    $(
+      impl Magnitude for $ty {
+        const ONE: Self = 1;
+      }
+
+      impl_coord!($ty => $ty);
+    )*
+  };
+}
+
+impl_magnitude!(u8, u16, u32, u64, u128, usize);
+impl_coord!(
+  i8 => u8,
+  i16 => u16,
+  i32 => u32,
+  i64 => u64,
+  i128 => u128,
+  isize => usize
+);
+
+/// A point in `N` dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T, const N: usize>(pub [T; N]);
+
+impl<T: Coord, const N: usize> Point<T, N> {
+  /// Parses `N` numbers with `separator` between them, which may be negative
+  /// if `T` is signed.
+  pub fn parse(
+    cursor: &mut Cursor<'_>,
+    separator: char,
+  ) -> Result<Self, ParseError> {
+    let mut point = Self::default();
+    for (i, coord) in point.0.iter_mut().enumerate() {
+      if i != 0 {
+        cursor.expect(separator)?;
+      }
+      *coord = if T::SIGNED {
+        cursor.signed_number()?
+      } else {
+        cursor.number()?
+      };
+    }
+    Ok(point)
+  }
+
+  fn diffs(self, other: Self) -> impl Iterator<Item = T::Magnitude> {
+    self.0.into_iter().zip(other.0).map(|(a, b)| a.abs_diff(b))
+  }
+
+  /// The square of the Euclidean distance, which orders points the same way
+  /// without leaving the integers.
+  pub fn dist_squared(self, other: Self) -> T::Magnitude {
+    self.diffs(other).map(|diff| diff * diff).sum()
+  }
+
+  pub fn manhattan(self, other: Self) -> T::Magnitude {
+    self.diffs(other).sum()
+  }
+
+  pub fn chebyshev(self, other: Self) -> T::Magnitude {
+    self.diffs(other).max().unwrap_or_default()
+  }
+}
+
+impl<T: Default, const N: usize> Default for Point<T, N> {
+  fn default() -> Self {
+    Self(array::from_fn(|_| T::default()))
+  }
+}
+
+impl<T, const N: usize> From<[T; N]> for Point<T, N> {
+  fn from(coords: [T; N]) -> Self {
+    Self(coords)
+  }
+}
+
+impl<T, const N: usize> Index<usize> for Point<T, N> {
+  type Output = T;
+
+  fn index(&self, axis: usize) -> &T {
+    &self.0[axis]
+  }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Point<T, N> {
+  fn index_mut(&mut self, axis: usize) -> &mut T {
+    &mut self.0[axis]
+  }
+}
+
+/// An axis-aligned box, including both of its corners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Aabb<T, const N: usize> {
+  pub min: Point<T, N>,
+  pub max: Point<T, N>,
+}
+
+impl<T: Coord, const N: usize> Aabb<T, N> {
+  /// The smallest box containing two opposite corners, in any order.
+  pub fn from_corners(a: Point<T, N>, b: Point<T, N>) -> Self {
+    Self {
+      min: Point(array::from_fn(|axis| a[axis].min(b[axis]))),
+      max: Point(array::from_fn(|axis| a[axis].max(b[axis]))),
+    }
+  }
+
+  /// How many integer points the box covers: its area in two dimensions, or
+  /// its volume in three.
+  pub fn area(&self) -> T::Magnitude {
+    self
+      .min
+      .diffs(self.max)
+      .map(|side| side + T::Magnitude::ONE)
+      .product()
+  }
+
+  pub fn contains(&self, point: Point<T, N>) -> bool {
+    (0..N).all(|axis| {
+      self.min[axis] <= point[axis] && point[axis] <= self.max[axis]
+    })
+  }
+
+  pub fn intersection(&self, other: &Self) -> Option<Self> {
+    let min = Point(array::from_fn(|axis| self.min[axis].max(other.min[axis])));
+    let max = Point(array::from_fn(|axis| self.max[axis].min(other.max[axis])));
+    (0..N)
+      .all(|axis| min[axis] <= max[axis])
+      .then_some(Self { min, max })
+  }
+
+  /// Whether the open interiors of two boxes overlap, so that boxes which
+  /// only share an edge do not count.
+  pub fn overlaps_interior(&self, other: &Self) -> bool {
+    (0..N).all(|axis| {
+      self.min[axis] < other.max[axis] && other.min[axis] < self.max[axis]
+    })
+  }
+}
+
+/// The edges of a polygon, closing it by joining the last vertex to the
+/// first.
+pub fn edges<T: Copy>(vertices: &[T]) -> impl Iterator<Item = [T; 2]> {
+  vertices
+    .iter()
+    .zip(vertices.iter().cycle().skip(1))
+    .map(|(&a, &b)| [a, b])
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn measures_distances() {
+    let a = Point([-1i64, 2, 3]);
+    let b = Point([2, -2, 3]);
+    assert_eq!(a.dist_squared(b), 25);
+    assert_eq!(a.manhattan(b), 7);
+    assert_eq!(a.chebyshev(b), 4);
+    assert_eq!(Point([7u64, 1]).manhattan(Point([2, 3])), 7);
+  }
+
+  #[test]
+  fn measures_boxes() {
+    let a = Aabb::from_corners(Point([9u64, 7]), Point([2, 3]));
+    assert_eq!(a.min, Point([2, 3]));
+    assert_eq!(a.area(), 40);
+    assert!(a.contains(Point([9, 3])));
+    assert!(!a.contains(Point([10, 3])));
+
+    let b = Aabb::from_corners(Point([9, 1]), Point([12, 3]));
+    assert_eq!(
+      a.intersection(&b),
+      Some(Aabb::from_corners(Point([9, 3]), Point([9, 3])))
+    );
+    assert!(!a.overlaps_interior(&b));
+    assert!(
+      a.overlaps_interior(&Aabb::from_corners(Point([8, 0]), Point([8, 5])))
+    );
+  }
+
+  #[test]
+  fn parses_points() {
+    let parse = |input| {
+      let mut cursor = Cursor::new(0, input);
+      Point::<i64, 3>::parse(&mut cursor, ',').map(|point| (point, cursor))
+    };
+    let (point, cursor) = parse("-3,0,12 rest").unwrap();
+    assert_eq!(point, Point([-3, 0, 12]));
+    assert_eq!(cursor.rest(), " rest");
+    assert!(parse("3,-,1").is_err());
+
+    let mut cursor = Cursor::new(0, "1,-3");
+    assert!(Point::<u64, 2>::parse(&mut cursor, ',').is_err());
+  }
+
+  #[test]
+  fn closes_polygons() {
+    assert_eq!(
+      edges(&[1, 2, 3]).collect::<Vec<_>>(),
+      [[1, 2], [2, 3], [3, 1]]
+    );
+    assert_eq!(edges::<u8>(&[]).count(), 0);
+  }
+}
@@ -0,0 +1,270 @@
+use std::{
+  fmt,
+  ops::{Index, IndexMut},
+};
+
+use crate::{ParseError, parse::Cursor};
+
+/// A position in a grid, as `(x, y)` with `y` growing downwards.
+pub type Pos = (usize, usize);
+
+const NEIGHBORS4: [[isize; 2]; 4] = [[0, -1], [-1, 0], [1, 0], [0, 1]];
+
+const NEIGHBORS8: [[isize; 2]; 8] = [
+  [-1, -1],
+  [0, -1],
+  [1, -1],
+  [-1, 0],
+  [1, 0],
+  [-1, 1],
+  [0, 1],
+  [1, 1],
+];
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+  cells: Box<[T]>,
+  width: usize,
+  height: usize,
+}
+
+impl<T> Grid<T> {
+  pub fn new(width: usize, height: usize, cell: T) -> Self
+  where
+    T: Clone,
+  {
+    Self {
+      cells: vec![cell; width * height].into_boxed_slice(),
+      width,
+      height,
+    }
+  }
+
+  /// Parses one row per line, mapping each character to a cell. Every row
+  /// must be as wide as the first, and a row that is not is reported where it
+  /// goes wrong.
+  pub fn parse(
+    cursor: Cursor<'_>,
+    expected: &str,
+    mut cell: impl FnMut(char) -> Option<T>,
+  ) -> Result<Self, ParseError> {
+    let mut cells = Vec::new();
+    let mut width = None;
+    let mut height = 0;
+
+    let mismatch = |line: &Cursor, width| {
+      line.error(format!("a row {width} wide, like the first"))
+    };
+
+    for mut line in cursor.lines() {
+      let mut row_width = 0;
+      while let Some(c) = line.peek() {
+        if let Some(width) = width.filter(|&width| width == row_width) {
+          return Err(mismatch(&line, width));
+        }
+        cells.push(cell(c).ok_or_else(|| line.error(expected))?);
+        line.bump();
+        row_width += 1;
+      }
+      match width {
+        None if row_width == 0 => return Err(line.error(expected)),
+        None => width = Some(row_width),
+        Some(width) if row_width < width => return Err(mismatch(&line, width)),
+        Some(_) => {}
+      }
+      height += 1;
+    }
+
+    let width = width.ok_or_else(|| cursor.error(expected))?;
+
+    Ok(Self {
+      cells: cells.into_boxed_slice(),
+      width,
+      height,
+    })
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn cells(&self) -> &[T] {
+    &self.cells
+  }
+
+  pub fn cells_mut(&mut self) -> &mut [T] {
+    &mut self.cells
+  }
+
+  pub fn contains(&self, (x, y): Pos) -> bool {
+    x < self.width && y < self.height
+  }
+
+  /// The index of a position in [`Grid::cells`].
+  pub fn index_of(&self, (x, y): Pos) -> usize {
+    debug_assert!(self.contains((x, y)));
+    y * self.width + x
+  }
+
+  /// The position of an index in [`Grid::cells`].
+  pub fn pos_of(&self, index: usize) -> Pos {
+    (index % self.width, index / self.width)
+  }
+
+  pub fn get(&self, pos: Pos) -> Option<&T> {
+    self.contains(pos).then(|| &self.cells[self.index_of(pos)])
+  }
+
+  pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+    if self.contains(pos) {
+      let index = self.index_of(pos);
+      Some(&mut self.cells[index])
+    } else {
+      None
+    }
+  }
+
+  pub fn row(&self, y: usize) -> &[T] {
+    &self.cells[y * self.width..(y + 1) * self.width]
+  }
+
+  pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+    &mut self.cells[y * self.width..(y + 1) * self.width]
+  }
+
+  pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+    self.cells.chunks_exact(self.width.max(1))
+  }
+
+  pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+    assert!(x < self.width, "column {x} out of bounds");
+    self.cells.iter().skip(x).step_by(self.width)
+  }
+
+  pub fn positions(&self) -> impl Iterator<Item = Pos> + use<T> {
+    let width = self.width;
+    (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+  }
+
+  /// Every cell together with its position.
+  pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+    self.positions().zip(self.cells.iter())
+  }
+
+  /// Steps from a position, if that stays inside the grid.
+  pub fn offset(&self, (x, y): Pos, [dx, dy]: [isize; 2]) -> Option<Pos> {
+    let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+    self.contains(pos).then_some(pos)
+  }
+
+  /// The orthogonally adjacent positions inside the grid.
+  pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+    NEIGHBORS4
+      .into_iter()
+      .filter_map(move |delta| self.offset(pos, delta))
+  }
+
+  /// The orthogonally and diagonally adjacent positions inside the grid.
+  pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+    NEIGHBORS8
+      .into_iter()
+      .filter_map(move |delta| self.offset(pos, delta))
+  }
+
+  pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+    Grid {
+      cells: self.cells.iter().map(f).collect(),
+      width: self.width,
+      height: self.height,
+    }
+  }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, pos: Pos) -> &T {
+    assert!(self.contains(pos), "{pos:?} out of bounds");
+    &self.cells[self.index_of(pos)]
+  }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+  fn index_mut(&mut self, pos: Pos) -> &mut T {
+    assert!(self.contains(pos), "{pos:?} out of bounds");
+    let index = self.index_of(pos);
+    &mut self.cells[index]
+  }
+}
+
+/// Writes one line per row, so that cells which display as the characters
+/// they were parsed from round-trip.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for row in self.rows() {
+      for cell in row {
+        write!(f, "{cell}")?;
+      }
+      writeln!(f)?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(Cursor::new(0, input), "'#' or '.'", |c| {
+      matches!(c, '#' | '.').then_some(c)
+    })
+  }
+
+  #[test]
+  fn round_trips() {
+    let input = "#..\n.#.\n..#\n#.#\n";
+    let grid = parse(input).unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 4));
+    assert_eq!(grid[(2, 3)], '#');
+    assert_eq!(grid.row(1), ['.', '#', '.']);
+    assert_eq!(grid.column(0).collect::<String>(), "#..#");
+    assert_eq!(grid.pos_of(grid.index_of((1, 2))), (1, 2));
+    assert_eq!(grid.to_string(), input);
+  }
+
+  #[test]
+  fn finds_neighbors() {
+    let mut grid = Grid::new(3, 2, 0);
+    assert_eq!(
+      grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+      [(1, 0), (0, 1)]
+    );
+    assert_eq!(grid.neighbors8((1, 1)).count(), 5);
+
+    for pos in grid.neighbors8((0, 0)).collect::<Vec<_>>() {
+      grid[pos] += 1;
+    }
+    assert_eq!(grid.to_string(), "010\n110\n");
+  }
+
+  #[test]
+  fn rejects_ragged_rows() {
+    assert_eq!(
+      parse("#.#\n#.\n").unwrap_err().to_string(),
+      "day00 line 2 col 3: expected a row 3 wide, like the first, \
+       found end of line"
+    );
+    assert_eq!(
+      parse("#.\n#..\n").unwrap_err().to_string(),
+      "day00 line 2 col 3: expected a row 2 wide, like the first, found '.'"
+    );
+    assert!(parse("#x\n").is_err());
+    assert!(parse("").is_err());
+  }
+}
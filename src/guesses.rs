@@ -0,0 +1,301 @@
+use std::{
+  fmt,
+  fs::{self, OpenOptions},
+  io::{self, Write},
+  path::PathBuf,
+  time::{Duration, SystemTime},
+};
+
+use crate::{
+  Part,
+  input::Inputs,
+  site::{self, Site, Verdict},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess {
+  /// Seconds since the Unix epoch.
+  pub time: u64,
+  pub part: Part,
+  pub answer: String,
+  pub verdict: Verdict,
+}
+
+fn verdict_name(verdict: Verdict) -> &'static str {
+  match verdict {
+    Verdict::Correct => "correct",
+    Verdict::TooHigh => "too-high",
+    Verdict::TooLow => "too-low",
+    Verdict::Wrong => "wrong",
+    Verdict::RateLimited(_) => "rate-limited",
+    Verdict::WrongLevel => "wrong-level",
+  }
+}
+
+fn verdict_from_name(name: &str) -> Option<Verdict> {
+  Some(match name {
+    "correct" => Verdict::Correct,
+    "too-high" => Verdict::TooHigh,
+    "too-low" => Verdict::TooLow,
+    "wrong" => Verdict::Wrong,
+    "rate-limited" => Verdict::RateLimited(Duration::ZERO),
+    "wrong-level" => Verdict::WrongLevel,
+    _ => return None,
+  })
+}
+
+/// One line per guess: `<time> <part> <answer> <verdict>`.
+impl fmt::Display for Guess {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{} {} {} {}",
+      self.time,
+      self.part,
+      self.answer,
+      verdict_name(self.verdict)
+    )
+  }
+}
+
+impl Guess {
+  fn parse(line: &str) -> Option<Self> {
+    let mut fields = line.split_whitespace();
+    let guess = Self {
+      time: fields.next()?.parse().ok()?,
+      part: fields.next()?.parse::<u8>().ok()?.try_into().ok()?,
+      answer: fields.next()?.into(),
+      verdict: verdict_from_name(fields.next()?)?,
+    };
+    fields.next().is_none().then_some(guess)
+  }
+}
+
+/// Why a guess was not sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+  Solved { answer: String },
+  AlreadyGuessed { verdict: Verdict },
+  AtLeast { bound: String },
+  AtMost { bound: String },
+}
+
+impl fmt::Display for Refusal {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Refusal::Solved { answer } => {
+        write!(f, "already solved, the answer was {answer}")
+      }
+      Refusal::AlreadyGuessed { verdict } => {
+        write!(f, "already guessed, and it was {verdict}")
+      }
+      Refusal::AtLeast { bound } => {
+        write!(f, "{bound} was already too high")
+      }
+      Refusal::AtMost { bound } => write!(f, "{bound} was already too low"),
+    }
+  }
+}
+
+/// Every answer submitted for a day, kept next to its input.
+#[derive(Debug, Clone)]
+pub struct Guesses {
+  path: PathBuf,
+  guesses: Vec<Guess>,
+}
+
+impl Guesses {
+  pub fn load(inputs: &Inputs, day: u8) -> io::Result<Self> {
+    let path = inputs.path(day, "guesses");
+
+    let text = match fs::read_to_string(&path) {
+      Ok(text) => text,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+      Err(err) => return Err(err),
+    };
+
+    let guesses = text
+      .lines()
+      .enumerate()
+      .filter(|(_, line)| !line.trim().is_empty())
+      .map(|(i, line)| {
+        Guess::parse(line).ok_or_else(|| {
+          io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} line {}: malformed guess", path.display(), i + 1),
+          )
+        })
+      })
+      .collect::<io::Result<_>>()?;
+
+    Ok(Self { path, guesses })
+  }
+
+  pub fn guesses(&self) -> &[Guess] {
+    &self.guesses
+  }
+
+  /// Refuses answers that the recorded verdicts already rule out.
+  pub fn check(&self, part: Part, answer: &str) -> Result<(), Refusal> {
+    let value = answer.parse::<u128>().ok();
+
+    for guess in self.guesses.iter().filter(|guess| guess.part == part) {
+      let bound = guess.answer.parse::<u128>().ok();
+      let refusal = match guess.verdict {
+        Verdict::Correct => Refusal::Solved {
+          answer: guess.answer.clone(),
+        },
+        verdict if verdict.is_judged() && guess.answer == answer => {
+          Refusal::AlreadyGuessed { verdict }
+        }
+        Verdict::TooHigh if value >= bound && bound.is_some() => {
+          Refusal::AtLeast {
+            bound: guess.answer.clone(),
+          }
+        }
+        Verdict::TooLow if value <= bound && value.is_some() => {
+          Refusal::AtMost {
+            bound: guess.answer.clone(),
+          }
+        }
+        _ => continue,
+      };
+      return Err(refusal);
+    }
+
+    Ok(())
+  }
+
+  pub fn record(&mut self, guess: Guess) -> io::Result<()> {
+    fs::create_dir_all(self.path.parent().expect("path has a parent"))?;
+    let mut file = OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(&self.path)?;
+    writeln!(file, "{guess}")?;
+    self.guesses.push(guess);
+    Ok(())
+  }
+}
+
+#[derive(Debug)]
+pub enum Error {
+  Refused(Refusal),
+  Site(site::Error),
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Error::Refused(refusal) => write!(f, "not submitting: {refusal}"),
+      Error::Site(err) => write!(f, "{err}"),
+    }
+  }
+}
+
+impl std::error::Error for Error {}
+
+impl From<site::Error> for Error {
+  fn from(err: site::Error) -> Self {
+    Error::Site(err)
+  }
+}
+
+impl From<io::Error> for Error {
+  fn from(err: io::Error) -> Self {
+    Error::Site(err.into())
+  }
+}
+
+/// Submits an answer unless the history rules it out, recording the verdict.
+pub fn submit(
+  site: &Site,
+  inputs: &Inputs,
+  day: u8,
+  part: Part,
+  answer: &str,
+) -> Result<Verdict, Error> {
+  let mut guesses = Guesses::load(inputs, day)?;
+  guesses.check(part, answer).map_err(Error::Refused)?;
+
+  let verdict = site.answer(day, part, answer)?;
+
+  let time = SystemTime::now()
+    .duration_since(SystemTime::UNIX_EPOCH)
+    .map_or(0, |elapsed| elapsed.as_secs());
+  guesses.record(Guess {
+    time,
+    part,
+    answer: answer.into(),
+    verdict,
+  })?;
+
+  Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::http::{TcpClient, mock::MockServer};
+
+  fn page(text: &str) -> (u16, String) {
+    (200, format!("<article><p>{text}</p></article>"))
+  }
+
+  #[test]
+  fn refuses_ruled_out_answers() {
+    let server = MockServer::start(vec![
+      page("That's not the right answer; your answer is too high."),
+      page("That's not the right answer; your answer is too low."),
+      page("You gave an answer too recently. You have 30s left to wait."),
+      page("That's the right answer!"),
+    ]);
+    let site = Site::new(&server.url, Some("abc".into()), Box::new(TcpClient));
+    let dir =
+      std::env::temp_dir().join(format!("aoc-guesses-{}", std::process::id()));
+    fs::remove_dir_all(&dir).ok();
+    let inputs = Inputs::new(&dir);
+
+    let submit = |answer| submit(&site, &inputs, 4, Part::One, answer);
+    let refused = |answer| match submit(answer) {
+      Err(Error::Refused(refusal)) => refusal,
+      result => panic!("expected a refusal, got {result:?}"),
+    };
+
+    assert_eq!(submit("100").unwrap(), Verdict::TooHigh);
+    assert_eq!(
+      refused("100"),
+      Refusal::AlreadyGuessed {
+        verdict: Verdict::TooHigh
+      }
+    );
+    assert_eq!(
+      refused("150"),
+      Refusal::AtLeast {
+        bound: "100".into()
+      }
+    );
+
+    assert_eq!(submit("10").unwrap(), Verdict::TooLow);
+    assert_eq!(refused("7"), Refusal::AtMost { bound: "10".into() });
+
+    assert_eq!(
+      submit("50").unwrap(),
+      Verdict::RateLimited(Duration::from_secs(30))
+    );
+    assert_eq!(submit("50").unwrap(), Verdict::Correct);
+    assert_eq!(
+      refused("51"),
+      Refusal::Solved {
+        answer: "50".into()
+      }
+    );
+
+    let guesses = Guesses::load(&inputs, 4).unwrap();
+    assert_eq!(guesses.guesses().len(), 4);
+    assert!(guesses.check(Part::Two, "100").is_ok());
+    assert_eq!(server.received().len(), 4);
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+}
@@ -0,0 +1,363 @@
+use std::{
+  io::{self, BufRead, BufReader, Write},
+  net::TcpStream,
+  process::{Command, Stdio},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+  pub method: &'static str,
+  pub url: String,
+  pub headers: Vec<(String, String)>,
+  pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+  pub status: u16,
+  pub body: String,
+}
+
+/// Something that can send a request, so that tests can swap the network for
+/// a local stand-in.
+pub trait Client {
+  fn send(&self, request: &Request) -> io::Result<Response>;
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+/// Refuses a header that would end its line early, and so smuggle in headers
+/// or config lines of its own.
+fn check_header(name: &str, value: &str) -> io::Result<()> {
+  if [name, value].iter().any(|part| part.contains(['\r', '\n'])) {
+    return Err(io::Error::new(
+      io::ErrorKind::InvalidInput,
+      format!("line break in header {name:?}"),
+    ));
+  }
+  Ok(())
+}
+
+/// Speaks plain HTTP/1.1 over a TCP socket.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TcpClient;
+
+impl Client for TcpClient {
+  fn send(&self, request: &Request) -> io::Result<Response> {
+    let rest = request.url.strip_prefix("http://").ok_or_else(|| {
+      io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("not an http:// url: {}", request.url),
+      )
+    })?;
+    let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let path = if path.is_empty() { "/" } else { path };
+    let address = if host.contains(':') {
+      host.to_owned()
+    } else {
+      format!("{host}:80")
+    };
+
+    let mut stream = TcpStream::connect(address)?;
+
+    let mut head = Vec::new();
+    write!(head, "{} {path} HTTP/1.1\r\n", request.method)?;
+    write!(head, "Host: {host}\r\nConnection: close\r\n")?;
+    for (name, value) in &request.headers {
+      check_header(name, value)?;
+      write!(head, "{name}: {value}\r\n")?;
+    }
+    if let Some(body) = &request.body {
+      write!(head, "Content-Length: {}\r\n", body.len())?;
+    }
+    write!(head, "\r\n")?;
+
+    stream.write_all(&head)?;
+    if let Some(body) = &request.body {
+      stream.write_all(body.as_bytes())?;
+    }
+    stream.flush()?;
+
+    read_response(BufReader::new(stream))
+  }
+}
+
+fn read_response(mut reader: impl BufRead) -> io::Result<Response> {
+  let mut line = String::new();
+  reader.read_line(&mut line)?;
+  let status = line
+    .split_whitespace()
+    .nth(1)
+    .and_then(|status| status.parse().ok())
+    .ok_or_else(|| invalid(format!("bad status line: {line:?}")))?;
+
+  let mut content_length = None;
+  let mut chunked = false;
+  loop {
+    line.clear();
+    reader.read_line(&mut line)?;
+    let header = line.trim_end();
+    if header.is_empty() {
+      break;
+    }
+    if let Some((name, value)) = header.split_once(':') {
+      let value = value.trim();
+      if name.eq_ignore_ascii_case("content-length") {
+        content_length = value.parse::<usize>().ok();
+      } else if name.eq_ignore_ascii_case("transfer-encoding") {
+        chunked = value.eq_ignore_ascii_case("chunked");
+      }
+    }
+  }
+
+  let mut body = Vec::new();
+  if chunked {
+    loop {
+      line.clear();
+      reader.read_line(&mut line)?;
+      let size = usize::from_str_radix(line.trim(), 16)
+        .map_err(|_| invalid(format!("bad chunk size: {line:?}")))?;
+      if size == 0 {
+        break;
+      }
+      let start = body.len();
+      body.resize(start + size, 0);
+      reader.read_exact(&mut body[start..])?;
+      line.clear();
+      reader.read_line(&mut line)?;
+    }
+  } else if let Some(length) = content_length {
+    body.resize(length, 0);
+    reader.read_exact(&mut body)?;
+  } else {
+    reader.read_to_end(&mut body)?;
+  }
+
+  let body = String::from_utf8(body).map_err(|err| invalid(err.to_string()))?;
+
+  Ok(Response { status, body })
+}
+
+/// A `header` line for a curl config file, quoted so that backslashes and
+/// quotes in the header come through as they are.
+fn curl_header(name: &str, value: &str) -> io::Result<String> {
+  check_header(name, value)?;
+  let header = format!("{name}: {value}")
+    .replace('\\', "\\\\")
+    .replace('"', "\\\"");
+  Ok(format!("header = \"{header}\""))
+}
+
+/// Hands HTTPS requests to the `curl` command, since std has no TLS. Headers
+/// go through curl's stdin rather than its arguments, keeping the session
+/// cookie out of the process list.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CurlClient;
+
+impl Client for CurlClient {
+  fn send(&self, request: &Request) -> io::Result<Response> {
+    let mut command = Command::new("curl");
+    command
+      .args(["--silent", "--show-error", "--config", "-"])
+      .args(["--request", request.method])
+      .args(["--write-out", "\n%{http_code}"]);
+    if let Some(body) = &request.body {
+      command.args(["--data-binary", body]);
+    }
+    command.arg(&request.url);
+
+    let config = request
+      .headers
+      .iter()
+      .map(|(name, value)| curl_header(name, value))
+      .collect::<io::Result<Vec<_>>>()?;
+
+    let mut child = command
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped())
+      .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    for line in config {
+      writeln!(stdin, "{line}")?;
+    }
+    drop(stdin);
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+      return Err(io::Error::other(format!(
+        "curl failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+      )));
+    }
+
+    let output = String::from_utf8(output.stdout)
+      .map_err(|err| invalid(err.to_string()))?;
+    let (body, status) = output
+      .rsplit_once('\n')
+      .ok_or_else(|| invalid("missing status from curl"))?;
+    let status = status
+      .parse()
+      .map_err(|_| invalid(format!("bad status from curl: {status:?}")))?;
+
+    Ok(Response {
+      status,
+      body: body.to_owned(),
+    })
+  }
+}
+
+/// Picks a client that can handle the url's scheme.
+pub fn client_for(url: &str) -> Box<dyn Client> {
+  if url.starts_with("http://") {
+    Box::new(TcpClient)
+  } else {
+    Box::new(CurlClient)
+  }
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+  use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::{Arc, Mutex},
+    thread,
+  };
+
+  /// A request as seen by the stand-in server.
+  #[derive(Debug, Clone)]
+  pub struct Received {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+  }
+
+  impl Received {
+    pub fn header(&self, name: &str) -> Option<&str> {
+      self
+        .headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+    }
+  }
+
+  /// Serves canned responses in order on a local port, recording requests.
+  pub struct MockServer {
+    pub url: String,
+    received: Arc<Mutex<Vec<Received>>>,
+  }
+
+  impl MockServer {
+    pub fn start(responses: Vec<(u16, String)>) -> Self {
+      let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+      let url = format!("http://{}", listener.local_addr().unwrap());
+      let received = Arc::new(Mutex::new(Vec::new()));
+
+      let log = Arc::clone(&received);
+      thread::spawn(move || {
+        for (status, body) in responses {
+          let (stream, _) = listener.accept().unwrap();
+          let mut reader = BufReader::new(stream);
+
+          let mut line = String::new();
+          reader.read_line(&mut line).unwrap();
+          let mut parts = line.split_whitespace();
+          let method = parts.next().unwrap_or_default().to_owned();
+          let path = parts.next().unwrap_or_default().to_owned();
+
+          let mut headers = Vec::new();
+          loop {
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+            match line.trim_end().split_once(':') {
+              Some((name, value)) => {
+                headers.push((name.to_owned(), value.trim().to_owned()));
+              }
+              None => break,
+            }
+          }
+
+          let length = headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+            .map_or(0, |(_, value)| value.parse().unwrap());
+          let mut body_bytes = vec![0; length];
+          reader.read_exact(&mut body_bytes).unwrap();
+
+          log.lock().unwrap().push(Received {
+            method,
+            path,
+            headers,
+            body: String::from_utf8(body_bytes).unwrap(),
+          });
+
+          let mut stream = reader.into_inner();
+          write!(
+            stream,
+            "HTTP/1.1 {status} X\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+          )
+          .unwrap();
+        }
+      });
+
+      Self { url, received }
+    }
+
+    pub fn received(&self) -> Vec<Received> {
+      self.received.lock().unwrap().clone()
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{mock::MockServer, *};
+
+  #[test]
+  fn sends_requests_over_tcp() {
+    let server = MockServer::start(vec![(200, "hello\n".into())]);
+
+    let response = TcpClient
+      .send(&Request {
+        method: "POST",
+        url: format!("{}/echo", server.url),
+        headers: vec![("X-Test".into(), "yes".into())],
+        body: Some("a=1".into()),
+      })
+      .unwrap();
+
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body, "hello\n");
+
+    let received = server.received();
+    assert_eq!(received[0].method, "POST");
+    assert_eq!(received[0].path, "/echo");
+    assert_eq!(received[0].header("x-test"), Some("yes"));
+    assert_eq!(received[0].body, "a=1");
+  }
+
+  #[test]
+  fn quotes_curl_headers() {
+    assert_eq!(
+      curl_header("Cookie", r#"session=a\"b"#).unwrap(),
+      r#"header = "Cookie: session=a\\\"b""#
+    );
+    assert!(curl_header("Cookie", "a\nurl = \"http://evil\"").is_err());
+    assert!(curl_header("Cookie", "a\rb").is_err());
+  }
+
+  #[test]
+  fn decodes_chunked_bodies() {
+    let raw = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+               4\r\nabcd\r\n3\r\nefg\r\n0\r\n\r\n";
+    let response = read_response(raw.as_bytes()).unwrap();
+    assert_eq!(response.body, "abcdefg");
+  }
+}
@@ -0,0 +1,135 @@
+use std::{
+  env, fs, io,
+  path::{Path, PathBuf},
+};
+
+/// Undoes what editors and downloads tend to do to an input: drops a UTF-8
+/// byte order mark, turns CRLF line endings into LF, strips whitespace from
+/// the end of every line and drops blank lines at the end, leaving a single
+/// final newline.
+pub fn normalize(input: &str) -> String {
+  let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+  let mut normalized = String::with_capacity(input.len());
+  for line in input.lines() {
+    normalized.push_str(line.trim_end());
+    normalized.push('\n');
+  }
+  let len = normalized.trim_end_matches('\n').len();
+  normalized.truncate(len);
+  if len != 0 {
+    normalized.push('\n');
+  }
+  normalized
+}
+
+/// Overrides the directory that puzzle inputs are read from.
+pub const INPUTS_ENV: &str = "AOC_INPUTS";
+
+/// Where puzzle inputs live: personal inputs in a directory kept out of git,
+/// falling back to the examples committed next to each day's solution.
+#[derive(Debug, Clone)]
+pub struct Inputs {
+  dir: PathBuf,
+}
+
+impl Inputs {
+  pub fn new(dir: impl Into<PathBuf>) -> Self {
+    Self { dir: dir.into() }
+  }
+
+  /// Uses `$AOC_INPUTS` if set, or the `inputs` directory of this crate.
+  pub fn from_env() -> Self {
+    match env::var_os(INPUTS_ENV) {
+      Some(dir) => Self::new(dir),
+      None => Self::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs")),
+    }
+  }
+
+  pub fn dir(&self) -> &Path {
+    &self.dir
+  }
+
+  pub fn path(&self, day: u8, name: &str) -> PathBuf {
+    self.dir.join(format!("day{day:02}")).join(name)
+  }
+
+  fn example_path(day: u8, name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+      .join("src")
+      .join(format!("day{day:02}"))
+      .join(name)
+  }
+
+  /// Every example committed for a day, in name order.
+  pub fn examples(day: u8) -> io::Result<Vec<String>> {
+    let dir = Self::example_path(day, "");
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+      let path = entry?.path();
+      if path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("input_test"))
+      {
+        paths.push(path);
+      }
+    }
+    paths.sort();
+    paths.into_iter().map(fs::read_to_string).collect()
+  }
+
+  /// Finds the named input for a day, if it exists anywhere.
+  pub fn resolve(&self, day: u8, name: &str) -> Option<PathBuf> {
+    [self.path(day, name), Self::example_path(day, name)]
+      .into_iter()
+      .find(|path| path.is_file())
+  }
+
+  /// Reads an input, normalized.
+  pub fn load(&self, day: u8, name: &str) -> io::Result<String> {
+    let path = self.resolve(day, name).ok_or_else(|| {
+      io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("no input {name:?} at {}", self.path(day, name).display()),
+      )
+    })?;
+    Ok(normalize(&fs::read_to_string(path)?))
+  }
+
+  /// Loads an input for a test, or explains why the test is being skipped.
+  pub fn load_or_skip(&self, day: u8, name: &str) -> Option<String> {
+    match self.load(day, name) {
+      Ok(input) => Some(input),
+      Err(err) if err.kind() == io::ErrorKind::NotFound => {
+        eprintln!("skipping day{day:02} {name}: {err}");
+        None
+      }
+      Err(err) => panic!("failed to load day{day:02} {name}: {err}"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn falls_back_to_examples() {
+    let inputs = Inputs::new("/nonexistent");
+    assert_eq!(
+      inputs.path(1, "input"),
+      Path::new("/nonexistent/day01/input")
+    );
+    assert!(inputs.load(1, "input_test").unwrap().starts_with("L68\n"));
+    assert!(inputs.load_or_skip(1, "input").is_none());
+    assert_eq!(Inputs::examples(11).unwrap().len(), 2);
+  }
+
+  #[test]
+  fn normalizes_inputs() {
+    assert_eq!(normalize("\u{feff}L1\r\nR2  \r\n\r\n\n"), "L1\nR2\n");
+    assert_eq!(normalize(" 1 2\n\n 3\t\n"), " 1 2\n\n 3\n");
+    assert_eq!(normalize("no newline"), "no newline\n");
+    assert_eq!(normalize("\n \n"), "");
+  }
+}
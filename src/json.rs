@@ -0,0 +1,362 @@
+use std::fmt::{self, Write};
+
+/// A JSON document. Integers are kept apart from floats so that large
+/// answers survive a round trip exactly.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+  Null,
+  Bool(bool),
+  Int(i128),
+  Float(f64),
+  String(String),
+  Array(Vec<Value>),
+  Object(Vec<(String, Value)>),
+}
+
+impl Value {
+  pub fn get(&self, key: &str) -> Option<&Value> {
+    match self {
+      Value::Object(fields) => fields
+        .iter()
+        .find_map(|(name, value)| (name == key).then_some(value)),
+      _ => None,
+    }
+  }
+
+  pub fn as_int(&self) -> Option<i128> {
+    match self {
+      Value::Int(value) => Some(*value),
+      _ => None,
+    }
+  }
+
+  pub fn as_str(&self) -> Option<&str> {
+    match self {
+      Value::String(value) => Some(value),
+      _ => None,
+    }
+  }
+
+  pub fn as_array(&self) -> Option<&[Value]> {
+    match self {
+      Value::Array(values) => Some(values),
+      _ => None,
+    }
+  }
+
+  pub fn parse(text: &str) -> Result<Value, String> {
+    let mut parser = Parser { text, pos: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos == text.len() {
+      Ok(value)
+    } else {
+      Err(parser.error("end of input"))
+    }
+  }
+}
+
+impl From<&str> for Value {
+  fn from(value: &str) -> Self {
+    Value::String(value.into())
+  }
+}
+
+impl From<String> for Value {
+  fn from(value: String) -> Self {
+    Value::String(value)
+  }
+}
+
+impl From<bool> for Value {
+  fn from(value: bool) -> Self {
+    Value::Bool(value)
+  }
+}
+
+impl From<f64> for Value {
+  fn from(value: f64) -> Self {
+    Value::Float(value)
+  }
+}
+
+macro_rules! impl_value_from_int {
+  ($($ty:ty),*) => {
+    $(
+      impl From<$ty> for Value {
+        fn from(value: $ty) -> Self {
+          Value::Int(value.into())
+        }
+      }
+    )*
+  };
+}
+
+impl_value_from_int!(u8, u16, u32, u64, i64);
+
+impl From<usize> for Value {
+  fn from(value: usize) -> Self {
+    Value::Int(value as i128)
+  }
+}
+
+impl From<isize> for Value {
+  fn from(value: isize) -> Self {
+    Value::Int(value as i128)
+  }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+  fn from(value: Option<T>) -> Self {
+    value.map_or(Value::Null, Into::into)
+  }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+  f.write_char('"')?;
+  for c in s.chars() {
+    match c {
+      '"' => f.write_str("\\\"")?,
+      '\\' => f.write_str("\\\\")?,
+      '\n' => f.write_str("\\n")?,
+      '\r' => f.write_str("\\r")?,
+      '\t' => f.write_str("\\t")?,
+      c if c.is_control() => write!(f, "\\u{:04x}", u32::from(c))?,
+      c => f.write_char(c)?,
+    }
+  }
+  f.write_char('"')
+}
+
+/// Writes compact JSON, or indented JSON with `{:#}`.
+impl fmt::Display for Value {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fn write(
+      value: &Value,
+      f: &mut fmt::Formatter<'_>,
+      depth: usize,
+    ) -> fmt::Result {
+      let pretty = f.alternate();
+      let newline = |f: &mut fmt::Formatter<'_>, depth: usize| {
+        if pretty {
+          write!(f, "\n{:width$}", "", width = 2 * depth)
+        } else {
+          Ok(())
+        }
+      };
+
+      match value {
+        Value::Null => f.write_str("null"),
+        Value::Bool(value) => write!(f, "{value}"),
+        Value::Int(value) => write!(f, "{value}"),
+        Value::Float(value) => {
+          if value.is_finite() {
+            write!(f, "{value}")
+          } else {
+            f.write_str("null")
+          }
+        }
+        Value::String(value) => write_string(f, value),
+        Value::Array(values) if values.is_empty() => f.write_str("[]"),
+        Value::Array(values) => {
+          f.write_char('[')?;
+          for (i, value) in values.iter().enumerate() {
+            if i != 0 {
+              f.write_char(',')?;
+            }
+            newline(f, depth + 1)?;
+            write(value, f, depth + 1)?;
+          }
+          newline(f, depth)?;
+          f.write_char(']')
+        }
+        Value::Object(fields) if fields.is_empty() => f.write_str("{}"),
+        Value::Object(fields) => {
+          f.write_char('{')?;
+          for (i, (name, value)) in fields.iter().enumerate() {
+            if i != 0 {
+              f.write_char(',')?;
+            }
+            newline(f, depth + 1)?;
+            write_string(f, name)?;
+            f.write_str(if pretty { ": " } else { ":" })?;
+            write(value, f, depth + 1)?;
+          }
+          newline(f, depth)?;
+          f.write_char('}')
+        }
+      }
+    }
+
+    write(self, f, 0)
+  }
+}
+
+struct Parser<'a> {
+  text: &'a str,
+  pos: usize,
+}
+
+impl Parser<'_> {
+  fn error(&self, expected: &str) -> String {
+    format!("expected {expected} at byte {}", self.pos)
+  }
+
+  fn peek(&self) -> Option<u8> {
+    self.text.as_bytes().get(self.pos).copied()
+  }
+
+  fn skip_whitespace(&mut self) {
+    while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+      self.pos += 1;
+    }
+  }
+
+  fn eat(&mut self, token: &str) -> bool {
+    let matches = self.text[self.pos..].starts_with(token);
+    if matches {
+      self.pos += token.len();
+    }
+    matches
+  }
+
+  fn expect(&mut self, token: &str) -> Result<(), String> {
+    if self.eat(token) {
+      Ok(())
+    } else {
+      Err(self.error(&format!("{token:?}")))
+    }
+  }
+
+  fn value(&mut self) -> Result<Value, String> {
+    self.skip_whitespace();
+    match self.peek() {
+      Some(b'n') => self.expect("null").map(|()| Value::Null),
+      Some(b't') => self.expect("true").map(|()| Value::Bool(true)),
+      Some(b'f') => self.expect("false").map(|()| Value::Bool(false)),
+      Some(b'"') => self.string().map(Value::String),
+      Some(b'[') => {
+        self.pos += 1;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if !self.eat("]") {
+          loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            if self.eat("]") {
+              break;
+            }
+            self.expect(",")?;
+          }
+        }
+        Ok(Value::Array(values))
+      }
+      Some(b'{') => {
+        self.pos += 1;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if !self.eat("}") {
+          loop {
+            self.skip_whitespace();
+            let name = self.string()?;
+            self.skip_whitespace();
+            self.expect(":")?;
+            fields.push((name, self.value()?));
+            self.skip_whitespace();
+            if self.eat("}") {
+              break;
+            }
+            self.expect(",")?;
+          }
+        }
+        Ok(Value::Object(fields))
+      }
+      Some(b'-' | b'0'..=b'9') => self.number(),
+      _ => Err(self.error("value")),
+    }
+  }
+
+  fn number(&mut self) -> Result<Value, String> {
+    let start = self.pos;
+    while matches!(
+      self.peek(),
+      Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+    ) {
+      self.pos += 1;
+    }
+    let token = &self.text[start..self.pos];
+    if let Ok(value) = token.parse::<i128>() {
+      Ok(Value::Int(value))
+    } else if let Ok(value) = token.parse::<f64>() {
+      Ok(Value::Float(value))
+    } else {
+      self.pos = start;
+      Err(self.error("number"))
+    }
+  }
+
+  fn string(&mut self) -> Result<String, String> {
+    self.expect("\"")?;
+    let mut value = String::new();
+    loop {
+      let rest = &self.text[self.pos..];
+      let mut chars = rest.chars();
+      let c = chars.next().ok_or_else(|| self.error("'\"'"))?;
+      self.pos += c.len_utf8();
+      match c {
+        '"' => return Ok(value),
+        '\\' => {
+          let escape = chars.next().ok_or_else(|| self.error("escape"))?;
+          self.pos += 1;
+          value.push(match escape {
+            '"' => '"',
+            '\\' => '\\',
+            '/' => '/',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'u' => {
+              let hex = self.text.get(self.pos..self.pos + 4);
+              let c = hex
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .and_then(char::from_u32)
+                .ok_or_else(|| self.error("unicode escape"))?;
+              self.pos += 4;
+              c
+            }
+            _ => return Err(self.error("escape")),
+          });
+        }
+        c => value.push(c),
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn round_trips() {
+    let value = Value::Object(vec![
+      ("day".into(), 8u8.into()),
+      ("answer".into(), u64::MAX.into()),
+      ("median".into(), 1.5.into()),
+      ("name".into(), "a \"quoted\"\nline".into()),
+      ("parts".into(), Value::Array(vec![Value::Null, true.into()])),
+    ]);
+
+    assert_eq!(Value::parse(&value.to_string()), Ok(value.clone()));
+    assert_eq!(Value::parse(&format!("{value:#}")), Ok(value));
+  }
+
+  #[test]
+  fn rejects_garbage() {
+    assert!(Value::parse("{\"a\": }").is_err());
+    assert!(Value::parse("[1, 2").is_err());
+    assert!(Value::parse("1 2").is_err());
+  }
+}
@@ -11,3 +11,75 @@ pub mod day09;
 pub mod day10;
 pub mod day11;
 pub mod day12;
+
+pub mod bench;
+pub mod calendar;
+pub mod differential;
+pub mod fuzz;
+pub mod geom;
+pub mod grid;
+pub mod guesses;
+pub mod http;
+pub mod input;
+pub mod json;
+pub mod manifest;
+pub mod params;
+pub mod parse;
+pub mod rng;
+pub mod sandbox;
+pub mod scaffold;
+pub mod site;
+mod solution;
+pub mod trace;
+pub mod union_find;
+
+pub use parse::ParseError;
+pub use solution::{Answer, Day, Part, Solution, Solve};
+
+pub static DAYS: &[Day] = &[
+  Day::of::<day01::Puzzle>(),
+  Day::of::<day02::Puzzle>(),
+  Day::of::<day03::Puzzle>(),
+  Day::of::<day04::Puzzle>(),
+  Day::of::<day05::Puzzle>(),
+  Day::of::<day06::Puzzle>(),
+  Day::of::<day07::Puzzle>(),
+  Day::of::<day08::Puzzle>(),
+  Day::of::<day09::Puzzle>(),
+  Day::of::<day10::Puzzle>(),
+  Day::of::<day11::Puzzle>(),
+  Day::of::<day12::Puzzle>(),
+];
+
+pub fn day(number: u8) -> Option<&'static Day> {
+  DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn generates_solvable_inputs() {
+    for day in DAYS {
+      for seed in 0..8 {
+        for size in [0, 1, 3, 20] {
+          let mut rng = rng::Rng::new(seed);
+          let params = day.generate_params(&mut rng);
+          let input = day.generate(&mut rng, size, &params);
+          let parsed = day.parse(&input, &params).unwrap_or_else(|err| {
+            panic!("seed {seed} size {size}: {err}\n{input}")
+          });
+          for part in Part::ALL {
+            assert_eq!(
+              parsed.solve(part).is_some(),
+              day.parts.contains(&part),
+              "day {} part {part}",
+              day.number
+            );
+          }
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,618 @@
+use std::{
+  collections::HashMap,
+  env, fs,
+  io::{self, Read},
+  panic,
+  path::Path,
+  process::ExitCode,
+  str::FromStr,
+  thread,
+  time::Duration,
+};
+
+use advent_of_code_2025::{
+  DAYS, Day, Part,
+  bench::{self, Baseline, Config, format_duration},
+  calendar::{self, Calendar, Format, Isolation, Options, Row},
+  day as find_day,
+  fuzz::{self, Corpus},
+  guesses,
+  input::{self, Inputs},
+  manifest::Manifest,
+  params::{self, Params},
+  rng::Rng,
+  sandbox::Limits,
+  scaffold::scaffold,
+  site::{self, Fetched, Site, Verdict},
+};
+
+const USAGE: &str = "\
+usage: aoc run <day> [<part>] [-i|--input <path>|-] [--inputs <dir>]
+               [--format text|json|csv] [--deadline <ms>] [--stack <MiB>]
+               [--param <name>=<value>]...
+       aoc bench [<day> [<part>]] [-i|--input <path>|-] [--inputs <dir>]
+                 [--param <name>=<value>]...
+                 [--warmup <ms>] [--samples <n>] [--max-time <s>]
+                 [--save <path>] [--baseline <path>] [--threshold <percent>]
+       aoc all [--inputs <dir>] [--jobs <n>] [--format text|json|csv]
+               [--deadline <ms>] [--stack <MiB>] [--isolate thread|process]
+       aoc trace <day> [-i|--input <path>|-] [--inputs <dir>]
+                 [--format text|json|csv] [--param <name>=<value>]...
+       aoc fetch <day> [--inputs <dir>]
+       aoc submit <day> <part> [-i|--input <path>|-] [--inputs <dir>]
+                  [--param <name>=<value>]...
+       aoc new <day> [--root <dir>] [--inputs <dir>]
+       aoc gen <day> [--seed <n>] [--size <n>] [--param <name>=<value>]...
+       aoc fuzz [<day>] [--seed <n>] [--runs <n>] [--timeout <ms>]
+                [--corpus <dir>]";
+
+fn usage(mut err: String) -> String {
+  err.push('\n');
+  err.push_str(USAGE);
+  err
+}
+
+/// Positional arguments plus `--name value` options, of which only `--param`
+/// may be repeated. `-i` is short for `--input`.
+struct Args {
+  positional: Vec<String>,
+  options: HashMap<&'static str, String>,
+  params: Vec<String>,
+}
+
+impl Args {
+  fn parse(
+    mut args: impl Iterator<Item = String>,
+    names: &[&'static str],
+    max_positional: usize,
+  ) -> Result<Self, String> {
+    let mut positional = Vec::new();
+    let mut options = HashMap::new();
+    let mut params = Vec::new();
+
+    while let Some(arg) = args.next() {
+      let long = match arg.as_str() {
+        "-i" => Some("input"),
+        arg => arg.strip_prefix("--"),
+      };
+      if let Some(name) = long {
+        let name = names
+          .iter()
+          .find(|known| **known == name)
+          .ok_or_else(|| format!("unknown option: {arg}"))?;
+        let value = args
+          .next()
+          .ok_or_else(|| format!("missing value for {arg}"))?;
+        if *name == "param" {
+          params.push(value);
+        } else {
+          options.insert(*name, value);
+        }
+      } else if positional.len() < max_positional {
+        positional.push(arg);
+      } else {
+        return Err(format!("unexpected argument: {arg}"));
+      }
+    }
+
+    Ok(Self {
+      positional,
+      options,
+      params,
+    })
+  }
+
+  fn option<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+    self
+      .options
+      .get(name)
+      .map(|value| {
+        value
+          .parse()
+          .map_err(|_| format!("invalid value for --{name}: {value}"))
+      })
+      .transpose()
+  }
+
+  fn positional<T: FromStr>(
+    &self,
+    index: usize,
+    name: &str,
+  ) -> Result<Option<T>, String> {
+    self
+      .positional
+      .get(index)
+      .map(|value| {
+        value
+          .parse()
+          .map_err(|_| format!("invalid {name}: {value}"))
+      })
+      .transpose()
+  }
+
+  fn day(&self, index: usize) -> Result<Option<&'static Day>, String> {
+    self
+      .positional(index, "day")?
+      .map(|day| find_day(day).ok_or(format!("day {day} is not implemented")))
+      .transpose()
+  }
+
+  fn part(&self, index: usize) -> Result<Option<Part>, String> {
+    self
+      .positional::<u8>(index, "part")?
+      .map(|part| {
+        Part::try_from(part).map_err(|_| format!("part must be 1 or 2: {part}"))
+      })
+      .transpose()
+  }
+
+  /// Reads `--deadline` and `--stack`, keeping the defaults for either one
+  /// that is missing.
+  fn limits(&self) -> Result<Limits, String> {
+    let defaults = Limits::default();
+    Ok(Limits {
+      deadline: self
+        .option("deadline")?
+        .map_or(defaults.deadline, Duration::from_millis),
+      stack_size: self
+        .option::<usize>("stack")?
+        .map_or(defaults.stack_size, |mib| mib << 20),
+    })
+  }
+
+  /// The day's parameters, with every `--param` applied to its defaults.
+  fn params(&self, day: &Day) -> Result<Params, String> {
+    let overrides = self
+      .params
+      .iter()
+      .map(|param| params::split_override(param))
+      .collect::<Result<Vec<_>, _>>()?;
+    day.resolve(overrides)
+  }
+
+  fn inputs(&self) -> Inputs {
+    self
+      .options
+      .get("inputs")
+      .map_or_else(Inputs::from_env, Inputs::new)
+  }
+
+  /// Reads `--input`, falling back to the day's input in the inputs directory,
+  /// and normalizes it.
+  fn read_input(&self, day: u8) -> Result<String, String> {
+    match self.options.get("input").map(String::as_str) {
+      None => self
+        .inputs()
+        .load(day, "input")
+        .map_err(|err| err.to_string()),
+      Some("-") => {
+        let mut buf = String::new();
+        io::stdin()
+          .read_to_string(&mut buf)
+          .map_err(|err| format!("stdin: {err}"))?;
+        Ok(input::normalize(&buf))
+      }
+      Some(path) => fs::read_to_string(path)
+        .map(|input| input::normalize(&input))
+        .map_err(|err| format!("{path}: {err}")),
+    }
+  }
+}
+
+fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+  let args = Args::parse(
+    args,
+    &["input", "inputs", "format", "deadline", "stack", "param"],
+    2,
+  )
+  .map_err(usage)?;
+
+  let day = args.day(0)?.ok_or_else(|| usage("missing day".into()))?;
+  let parts = match args.part(1)? {
+    Some(part) if !day.parts.contains(&part) => {
+      return Err(format!("day {} has no part {part}", day.number));
+    }
+    Some(part) => vec![part],
+    None => day.parts.to_vec(),
+  };
+  let format = args.option("format")?.unwrap_or(Format::Text);
+  let limits = args.limits()?;
+  let params = args.params(day)?;
+
+  let input = args.read_input(day.number)?;
+
+  let mut rows = Vec::new();
+  for part in parts {
+    if let Some(solved) = calendar::solve(day, &input, part, &params, limits) {
+      rows.push(Row::new(day, part, solved, None));
+    }
+  }
+
+  if format != Format::Text {
+    // The records report every part, but a failed one still fails the run.
+    let failure = rows.iter().find_map(|row| row.answer.clone().err());
+    let calendar = Calendar {
+      rows,
+      skipped: Vec::new(),
+      elapsed: Duration::ZERO,
+    };
+    print!("{}", calendar.render(format));
+    return failure.map_or(Ok(()), Err);
+  }
+
+  for row in rows {
+    let answer = row.answer?;
+    println!(
+      "day{:02} part{}: {answer} ({})",
+      day.number,
+      row.part,
+      format_duration(row.parse + row.solve)
+    );
+  }
+
+  Ok(())
+}
+
+fn bench(args: impl Iterator<Item = String>) -> Result<(), String> {
+  let args = Args::parse(
+    args,
+    &[
+      "input",
+      "inputs",
+      "param",
+      "warmup",
+      "samples",
+      "max-time",
+      "save",
+      "baseline",
+      "threshold",
+    ],
+    2,
+  )
+  .map_err(usage)?;
+
+  let days = match args.day(0)? {
+    Some(day) => vec![day],
+    None if args.options.contains_key("input") => {
+      return Err(usage("--input needs a day".into()));
+    }
+    None if !args.params.is_empty() => {
+      return Err(usage("--param needs a day".into()));
+    }
+    None => DAYS.iter().collect(),
+  };
+  let parts = args.part(1)?.map_or(Part::ALL.to_vec(), |part| vec![part]);
+
+  let defaults = Config::default();
+  let config = Config {
+    warmup: args
+      .option("warmup")?
+      .map_or(defaults.warmup, Duration::from_millis),
+    samples: args.option("samples")?.unwrap_or(defaults.samples),
+    max_time: args
+      .option("max-time")?
+      .map_or(defaults.max_time, Duration::from_secs_f64),
+  };
+
+  let baseline = args
+    .options
+    .get("baseline")
+    .map(|path| Baseline::load(Path::new(path)))
+    .transpose()
+    .map_err(|err| err.to_string())?;
+  let threshold = args.option::<f64>("threshold")?.unwrap_or(10.0) / 100.0;
+
+  println!("{}", bench::HEADER);
+
+  let mut measurements = Vec::new();
+
+  for day in days {
+    let params = args.params(day)?;
+    let input = match args.read_input(day.number) {
+      Ok(input) => input,
+      Err(err) if args.positional.is_empty() => {
+        eprintln!("skipping day{:02}: {err}", day.number);
+        continue;
+      }
+      Err(err) => return Err(err),
+    };
+
+    for &part in &parts {
+      let Some(measurement) =
+        bench::measure(day, &input, part, &params, &config)
+          .map_err(|err| err.to_string())?
+      else {
+        continue;
+      };
+
+      println!("{measurement}");
+      measurements.push(measurement);
+    }
+  }
+
+  if let Some(path) = args.options.get("save") {
+    Baseline {
+      measurements: measurements.clone(),
+    }
+    .save(Path::new(path))
+    .map_err(|err| format!("{path}: {err}"))?;
+    println!("saved baseline to {path}");
+  }
+
+  if let Some(baseline) = baseline {
+    let regressions = bench::compare(&baseline, &measurements, threshold);
+    if !regressions.is_empty() {
+      for regression in &regressions {
+        eprintln!("regression: {regression}");
+      }
+      return Err(format!(
+        "{} regression(s) beyond {:.0}%",
+        regressions.len(),
+        threshold * 100.0
+      ));
+    }
+    println!("no regressions beyond {:.0}%", threshold * 100.0);
+  }
+
+  Ok(())
+}
+
+fn all(args: impl Iterator<Item = String>) -> Result<(), String> {
+  let args = Args::parse(
+    args,
+    &["inputs", "jobs", "format", "deadline", "stack", "isolate"],
+    0,
+  )
+  .map_err(usage)?;
+
+  let options = Options {
+    jobs: match args.option("jobs")? {
+      Some(jobs) => jobs,
+      None => thread::available_parallelism().map_or(1, usize::from),
+    },
+    limits: args.limits()?,
+    isolation: match args.options.get("isolate").map(String::as_str) {
+      None | Some("thread") => Isolation::Thread,
+      Some("process") => Isolation::Process(
+        env::current_exe().map_err(|err| format!("aoc binary: {err}"))?,
+      ),
+      Some(isolate) => {
+        return Err(format!("invalid value for --isolate: {isolate}"));
+      }
+    },
+  };
+  let manifest =
+    Manifest::load(&Manifest::default_path()).map_err(|err| err.to_string())?;
+  let format = args.option("format")?.unwrap_or(Format::Text);
+  let days: Vec<&Day> = DAYS.iter().collect();
+
+  let calendar =
+    calendar::run(&days, &args.inputs(), "input", &manifest, &options);
+
+  for (day, err) in &calendar.skipped {
+    eprintln!("skipping day{day:02}: {err}");
+  }
+  print!("{}", calendar.render(format));
+
+  for row in &calendar.rows {
+    if let Err(err) = &row.answer {
+      eprintln!("day{:02} part{}: {err}", row.day, row.part);
+    }
+  }
+  match calendar.failures() {
+    0 => Ok(()),
+    failures => Err(format!("{failures} part(s) failed")),
+  }
+}
+
+fn trace(args: impl Iterator<Item = String>) -> Result<(), String> {
+  let args = Args::parse(args, &["input", "inputs", "format", "param"], 1)
+    .map_err(usage)?;
+
+  let day = args.day(0)?.ok_or_else(|| usage("missing day".into()))?;
+  let format = args.option("format")?.unwrap_or(Format::Text);
+  let params = args.params(day)?;
+
+  let input = args.read_input(day.number)?;
+  let trace = day
+    .parse(&input, &params)
+    .map_err(|err| err.to_string())?
+    .trace()
+    .ok_or_else(|| format!("day {} has no trace", day.number))?;
+
+  print!("{}", trace.render(format));
+
+  Ok(())
+}
+
+fn fetch(args: impl Iterator<Item = String>) -> Result<(), String> {
+  let args = Args::parse(args, &["inputs"], 1).map_err(usage)?;
+
+  let day = args
+    .positional::<u8>(0, "day")?
+    .ok_or_else(|| usage("missing day".into()))?;
+  if !(1..=site::LAST_DAY).contains(&day) {
+    return Err(format!(
+      "day must be between 1 and {}: {day}",
+      site::LAST_DAY
+    ));
+  }
+
+  match site::fetch(&Site::from_env(), &args.inputs(), day)
+    .map_err(|err| err.to_string())?
+  {
+    Fetched::Cached(path) => {
+      println!("day{day:02} input already cached at {}", path.display());
+    }
+    Fetched::Downloaded(path) => {
+      println!("downloaded day{day:02} input to {}", path.display());
+    }
+  }
+
+  Ok(())
+}
+
+fn submit(args: impl Iterator<Item = String>) -> Result<(), String> {
+  let args =
+    Args::parse(args, &["input", "inputs", "param"], 2).map_err(usage)?;
+
+  let day = args.day(0)?.ok_or_else(|| usage("missing day".into()))?;
+  let part = args.part(1)?.ok_or_else(|| usage("missing part".into()))?;
+  let params = args.params(day)?;
+
+  let input = args.read_input(day.number)?;
+  let answer = day
+    .solve(&input, part, &params)
+    .map_err(|err| err.to_string())?
+    .ok_or_else(|| format!("day {} has no part {part}", day.number))?
+    .to_string();
+
+  println!("submitting day{:02} part{part}: {answer}", day.number);
+
+  let verdict = guesses::submit(
+    &Site::from_env(),
+    &args.inputs(),
+    day.number,
+    part,
+    &answer,
+  )
+  .map_err(|err| err.to_string())?;
+
+  match verdict {
+    Verdict::Correct => {
+      println!("{verdict}");
+      Ok(())
+    }
+    verdict => Err(verdict.to_string()),
+  }
+}
+
+fn new(args: impl Iterator<Item = String>) -> Result<(), String> {
+  let args = Args::parse(args, &["root", "inputs"], 1).map_err(usage)?;
+
+  let day = args
+    .positional::<u8>(0, "day")?
+    .ok_or_else(|| usage("missing day".into()))?;
+  if !(1..=site::LAST_DAY).contains(&day) {
+    return Err(format!(
+      "day must be between 1 and {}: {day}",
+      site::LAST_DAY
+    ));
+  }
+
+  let root = args
+    .options
+    .get("root")
+    .map_or(Path::new(env!("CARGO_MANIFEST_DIR")), Path::new);
+
+  let files =
+    scaffold(root, &args.inputs(), day).map_err(|err| err.to_string())?;
+  for file in files {
+    println!("wrote {}", file.display());
+  }
+
+  Ok(())
+}
+
+fn generate(args: impl Iterator<Item = String>) -> Result<(), String> {
+  let args = Args::parse(args, &["seed", "size", "param"], 1).map_err(usage)?;
+
+  let day = args.day(0)?.ok_or_else(|| usage("missing day".into()))?;
+  let mut rng = Rng::new(args.option("seed")?.unwrap_or(0));
+  let size = args.option("size")?.unwrap_or(10);
+  let params = args.params(day)?;
+
+  print!("{}", day.generate(&mut rng, size, &params));
+
+  Ok(())
+}
+
+fn fuzz(args: impl Iterator<Item = String>) -> Result<(), String> {
+  let args = Args::parse(args, &["seed", "runs", "timeout", "corpus"], 1)
+    .map_err(usage)?;
+
+  let days = match args.day(0)? {
+    Some(day) => vec![day],
+    None => DAYS.iter().collect(),
+  };
+  let mut rng = Rng::new(args.option("seed")?.unwrap_or(0));
+  let runs = args.option("runs")?.unwrap_or(1000);
+  let limit = Duration::from_millis(args.option("timeout")?.unwrap_or(1000));
+  let corpus = Corpus::new(
+    args
+      .options
+      .get("corpus")
+      .map_or_else(Corpus::default_path, Into::into),
+  );
+
+  // Panics are the point here, and are reported below instead.
+  panic::set_hook(Box::new(|_| {}));
+
+  let mut crash_count = 0;
+  for day in days {
+    let mut seeds: Vec<_> = Inputs::examples(day.number)
+      .map_err(|err| err.to_string())?
+      .into_iter()
+      .map(|input| (input, day.defaults()))
+      .collect();
+    for size in [3, 10] {
+      let params = day.generate_params(&mut rng);
+      seeds.push((day.generate(&mut rng, size, &params), params));
+    }
+    for (_, input, params) in
+      corpus.cases(day).map_err(|err| err.to_string())?
+    {
+      seeds.push((input, params));
+    }
+
+    let crashes = fuzz::fuzz(day, &seeds, &mut rng, runs, limit);
+    for crash in &crashes {
+      let path = corpus
+        .record(crash.day, &crash.input, &crash.params)
+        .map_err(|err| err.to_string())?;
+      println!("day{:02} {} ({})", crash.day, crash.failure, path.display());
+    }
+    println!(
+      "day{:02}: {} crashes in {runs} runs",
+      day.number,
+      crashes.len()
+    );
+    crash_count += crashes.len();
+  }
+
+  if crash_count == 0 {
+    Ok(())
+  } else {
+    Err(format!("{crash_count} crashes"))
+  }
+}
+
+fn main() -> ExitCode {
+  let mut args = env::args().skip(1);
+
+  let result = match args.next().as_deref() {
+    Some("run") => run(args),
+    Some("bench") => bench(args),
+    Some("all") => all(args),
+    Some("trace") => trace(args),
+    Some("fetch") => fetch(args),
+    Some("submit") => submit(args),
+    Some("new") => new(args),
+    Some("gen") => generate(args),
+    Some("fuzz") => fuzz(args),
+    Some("-h" | "--help") => {
+      println!("{USAGE}");
+      return ExitCode::SUCCESS;
+    }
+    Some(command) => Err(usage(format!("unknown command: {command}"))),
+    None => Err(usage("missing command".into())),
+  };
+
+  match result {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(err) => {
+      eprintln!("error: {err}");
+      ExitCode::FAILURE
+    }
+  }
+}
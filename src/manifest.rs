@@ -0,0 +1,232 @@
+use std::{
+  error, fmt, fs, io,
+  path::{Path, PathBuf},
+};
+
+use crate::Part;
+
+/// One expected answer: the answer to `part` of `day` for the named input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+  pub day: u8,
+  pub input: String,
+  pub part: Part,
+  pub answer: String,
+}
+
+/// A parameter the named input of `day` is solved with, instead of the
+/// day's default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Override {
+  pub day: u8,
+  pub input: String,
+  pub name: String,
+  pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestError {
+  pub line: usize,
+  pub message: String,
+}
+
+impl fmt::Display for ManifestError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "line {}: {}", self.line, self.message)
+  }
+}
+
+impl error::Error for ManifestError {}
+
+/// The expected answers for every known input, read from a small subset of
+/// TOML: `[dayNN.<input>]` tables holding `part1` and `part2` keys, and any
+/// `params.<name>` keys the input needs.
+#[derive(Debug, Clone, Default)]
+pub struct Manifest {
+  entries: Vec<Entry>,
+  overrides: Vec<Override>,
+}
+
+impl Manifest {
+  pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml")
+  }
+
+  pub fn load(path: &Path) -> io::Result<Self> {
+    let text = fs::read_to_string(path)?;
+    Self::parse(&text).map_err(|err| {
+      io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {err}", path.display()),
+      )
+    })
+  }
+
+  pub fn parse(text: &str) -> Result<Self, ManifestError> {
+    let mut entries = Vec::<Entry>::new();
+    let mut overrides = Vec::<Override>::new();
+    let mut table: Option<(u8, &str)> = None;
+
+    for (index, line) in text.lines().enumerate() {
+      let error = |message: String| ManifestError {
+        line: index + 1,
+        message,
+      };
+
+      let line = strip_comment(line).trim();
+      if line.is_empty() {
+        continue;
+      }
+
+      if let Some(header) = line.strip_prefix('[') {
+        let header = header
+          .strip_suffix(']')
+          .ok_or_else(|| error("expected ']' after table name".into()))?;
+        let (day, input) = header.trim().split_once('.').ok_or_else(|| {
+          error(format!("expected [dayNN.<input>], found [{header}]"))
+        })?;
+        let day = day
+          .strip_prefix("day")
+          .and_then(|day| day.parse().ok())
+          .ok_or_else(|| error(format!("invalid day {day:?}")))?;
+        table = Some((day, input.trim_matches('"')));
+        continue;
+      }
+
+      let (day, input) = table
+        .ok_or_else(|| error("expected a [dayNN.<input>] table".into()))?;
+
+      let (key, value) = line.split_once('=').ok_or_else(|| {
+        error(format!("expected key = value, found {line:?}"))
+      })?;
+
+      let value = parse_value(value.trim()).map_err(error)?;
+
+      if let Some(name) = key.trim().strip_prefix("params.") {
+        if overrides.iter().any(|param| {
+          param.day == day && param.input == input && param.name == name
+        }) {
+          return Err(error(format!("duplicate parameter {name}")));
+        }
+        overrides.push(Override {
+          day,
+          input: input.to_owned(),
+          name: name.to_owned(),
+          value,
+        });
+        continue;
+      }
+
+      let part = match key.trim() {
+        "part1" => Part::One,
+        "part2" => Part::Two,
+        key => return Err(error(format!("unknown key {key:?}"))),
+      };
+
+      if entries.iter().any(|entry| {
+        entry.day == day && entry.input == input && entry.part == part
+      }) {
+        return Err(error(format!("duplicate answer for part {part}")));
+      }
+
+      entries.push(Entry {
+        day,
+        input: input.to_owned(),
+        part,
+        answer: value,
+      });
+    }
+
+    Ok(Self { entries, overrides })
+  }
+
+  pub fn entries(&self) -> &[Entry] {
+    &self.entries
+  }
+
+  pub fn expected(&self, day: u8, input: &str, part: Part) -> Option<&str> {
+    self
+      .entries
+      .iter()
+      .find(|entry| {
+        entry.day == day && entry.input == input && entry.part == part
+      })
+      .map(|entry| entry.answer.as_str())
+  }
+
+  /// The parameters set for the named input, as `(name, value)` pairs.
+  pub fn params(
+    &self,
+    day: u8,
+    input: &str,
+  ) -> impl Iterator<Item = (&str, &str)> {
+    self
+      .overrides
+      .iter()
+      .filter(move |param| param.day == day && param.input == input)
+      .map(|param| (param.name.as_str(), param.value.as_str()))
+  }
+}
+
+/// Cuts a line off at its first `#` outside a string.
+fn strip_comment(line: &str) -> &str {
+  let mut quoted = false;
+  for (i, c) in line.char_indices() {
+    match c {
+      '"' => quoted = !quoted,
+      '#' if !quoted => return &line[..i],
+      _ => {}
+    }
+  }
+  line
+}
+
+/// Reads an integer, which may contain `_` separators, or a quoted string.
+fn parse_value(value: &str) -> Result<String, String> {
+  if let Some(value) = value.strip_prefix('"') {
+    value
+      .strip_suffix('"')
+      .map(str::to_owned)
+      .ok_or_else(|| "unterminated string".into())
+  } else if !value.is_empty()
+    && value.chars().all(|c| c.is_ascii_digit() || c == '_')
+  {
+    Ok(value.replace('_', ""))
+  } else {
+    Err(format!("expected integer or string, found {value:?}"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_tables() {
+    let manifest = Manifest::parse(
+      "# comment\n[day02.input]\npart1 = 41_294_979_841 # trailing\n\n\
+       [day12.\"alice\"]\npart1 = \"460\"\nparams.start = \"you\"\n\
+       params.end = \"#out\" # trailing\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+      manifest.expected(2, "input", Part::One),
+      Some("41294979841")
+    );
+    assert_eq!(manifest.expected(12, "alice", Part::One), Some("460"));
+    assert_eq!(manifest.expected(2, "input", Part::Two), None);
+    assert_eq!(
+      manifest.params(12, "alice").collect::<Vec<_>>(),
+      [("start", "you"), ("end", "#out")]
+    );
+    assert_eq!(manifest.params(2, "input").count(), 0);
+  }
+
+  #[test]
+  fn reports_line_numbers() {
+    let err =
+      Manifest::parse("[day01.input]\npart1 = 1\npart3 = 2\n").unwrap_err();
+    assert_eq!(err.to_string(), "line 3: unknown key \"part3\"");
+  }
+}
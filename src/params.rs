@@ -0,0 +1,213 @@
+use std::{fmt, str::FromStr};
+
+/// What values a parameter takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+  /// A whole number in a range, which always fits in a `u32`.
+  Number { min: u32, max: u32 },
+  /// A word without whitespace, such as a device name.
+  Name,
+}
+
+impl Kind {
+  fn accepts(self, value: &str) -> bool {
+    match self {
+      Kind::Number { min, max } => value
+        .parse::<u32>()
+        .is_ok_and(|number| (min..=max).contains(&number)),
+      Kind::Name => !value.is_empty() && !value.contains(char::is_whitespace),
+    }
+  }
+}
+
+impl fmt::Display for Kind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match *self {
+      Kind::Number {
+        min: 0,
+        max: u32::MAX,
+      } => f.write_str("a number"),
+      Kind::Number { min, max } => write!(f, "a number from {min} to {max}"),
+      Kind::Name => f.write_str("a name"),
+    }
+  }
+}
+
+/// Something an answer depends on besides the input, which examples often
+/// set differently from real inputs, such as how many pairs day 8 connects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+  pub name: &'static str,
+  pub default: &'static str,
+  pub kind: Kind,
+}
+
+impl Param {
+  pub const fn number(name: &'static str, default: &'static str) -> Self {
+    Self {
+      name,
+      default,
+      kind: Kind::Number {
+        min: 0,
+        max: u32::MAX,
+      },
+    }
+  }
+
+  /// Limits a number to `min..=max`.
+  #[must_use]
+  pub const fn between(self, min: u32, max: u32) -> Self {
+    Self {
+      kind: Kind::Number { min, max },
+      ..self
+    }
+  }
+
+  pub const fn name(name: &'static str, default: &'static str) -> Self {
+    Self {
+      name,
+      default,
+      kind: Kind::Name,
+    }
+  }
+}
+
+/// The value of every parameter a day declares.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+  values: Vec<(Param, String)>,
+}
+
+impl Params {
+  pub fn defaults(declared: &[Param]) -> Self {
+    Self {
+      values: declared
+        .iter()
+        .map(|&param| (param, param.default.to_owned()))
+        .collect(),
+    }
+  }
+
+  /// Starts from the defaults, then applies `overrides` in order, so that
+  /// later ones win. Names that are not declared, and values of the wrong
+  /// kind, are errors.
+  pub fn resolve<'o>(
+    declared: &[Param],
+    overrides: impl IntoIterator<Item = (&'o str, &'o str)>,
+  ) -> Result<Self, String> {
+    let mut params = Self::defaults(declared);
+    for (name, value) in overrides {
+      let (param, slot) = params
+        .values
+        .iter_mut()
+        .find(|(param, _)| param.name == name)
+        .ok_or_else(|| format!("unknown parameter: {name}"))?;
+      if !param.kind.accepts(value) {
+        return Err(format!(
+          "parameter {name} must be {}: {value}",
+          param.kind
+        ));
+      }
+      value.clone_into(slot);
+    }
+    Ok(params)
+  }
+
+  /// Reads a value. Panics if the parameter is not declared or its value does
+  /// not fit `T`, which for the type its kind suggests `resolve` rules out.
+  pub fn get<T: FromStr>(&self, name: &str) -> T {
+    let (_, value) = self
+      .values
+      .iter()
+      .find(|(param, _)| param.name == name)
+      .unwrap_or_else(|| panic!("undeclared parameter: {name}"));
+    value
+      .parse()
+      .unwrap_or_else(|_| panic!("invalid parameter {name}: {value}"))
+  }
+
+  /// Every name and value, in the order they were declared.
+  pub fn iter(&self) -> impl Iterator<Item = (&'static str, &str)> {
+    self
+      .values
+      .iter()
+      .map(|(param, value)| (param.name, value.as_str()))
+  }
+
+  /// The names and values that differ from their defaults, as `--param`
+  /// would need to set them.
+  pub fn overrides(&self) -> impl Iterator<Item = (&'static str, &str)> {
+    self
+      .values
+      .iter()
+      .filter(|(param, value)| param.default != value)
+      .map(|(param, value)| (param.name, value.as_str()))
+  }
+}
+
+impl fmt::Display for Params {
+  /// The overrides as `name=value`, separated by spaces.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (i, (name, value)) in self.overrides().enumerate() {
+      if i != 0 {
+        f.write_str(" ")?;
+      }
+      write!(f, "{name}={value}")?;
+    }
+    Ok(())
+  }
+}
+
+/// Splits a `name=value` override, as given on the command line.
+pub fn split_override(text: &str) -> Result<(&str, &str), String> {
+  text
+    .split_once('=')
+    .filter(|(name, _)| !name.is_empty())
+    .ok_or_else(|| format!("expected name=value, found {text:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const DECLARED: &[Param] = &[
+    Param::number("count", "10"),
+    Param::name("start", "you"),
+    Param::number("digits", "12").between(1, 19),
+  ];
+
+  #[test]
+  fn resolves_overrides() {
+    let params = Params::defaults(DECLARED);
+    assert_eq!(params.get::<usize>("count"), 10);
+    assert_eq!(params.get::<String>("start"), "you");
+
+    let params =
+      Params::resolve(DECLARED, [("count", "3"), ("count", "1000")]).unwrap();
+    assert_eq!(params.get::<u64>("count"), 1000);
+    assert_eq!(
+      params.iter().collect::<Vec<_>>(),
+      [("count", "1000"), ("start", "you"), ("digits", "12")]
+    );
+    assert_eq!(params.to_string(), "count=1000");
+    assert_eq!(Params::defaults(DECLARED).to_string(), "");
+
+    assert_eq!(
+      Params::resolve(DECLARED, [("size", "3")]),
+      Err("unknown parameter: size".into())
+    );
+    assert_eq!(
+      Params::resolve(DECLARED, [("count", "-1")]),
+      Err("parameter count must be a number: -1".into())
+    );
+    assert!(Params::resolve(DECLARED, [("start", "a b")]).is_err());
+    assert_eq!(
+      Params::resolve(DECLARED, [("digits", "20")]),
+      Err("parameter digits must be a number from 1 to 19: 20".into())
+    );
+
+    assert_eq!(split_override("count=3"), Ok(("count", "3")));
+    assert!(split_override("=3").is_err());
+    assert!(split_override("count").is_err());
+  }
+}
@@ -0,0 +1,323 @@
+use std::{error, fmt, str::FromStr};
+
+/// A malformed puzzle input, pointing at the offending line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+  pub day: u8,
+  pub line: usize,
+  pub column: usize,
+  pub expected: String,
+  pub found: String,
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "day{:02} line {} col {}: expected {}, found {}",
+      self.day, self.line, self.column, self.expected, self.found
+    )
+  }
+}
+
+impl error::Error for ParseError {}
+
+/// A view into a puzzle input that remembers where it is in the whole input,
+/// so that errors can be reported by line and column.
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor<'a> {
+  day: u8,
+  src: &'a str,
+  start: usize,
+  end: usize,
+}
+
+impl<'a> Cursor<'a> {
+  pub fn new(day: u8, src: &'a str) -> Self {
+    Self {
+      day,
+      src,
+      start: 0,
+      end: src.len(),
+    }
+  }
+
+  fn sub(&self, s: &'a str) -> Self {
+    let start = s.as_ptr().addr() - self.src.as_ptr().addr();
+    Self {
+      start,
+      end: start + s.len(),
+      ..*self
+    }
+  }
+
+  pub fn rest(&self) -> &'a str {
+    &self.src[self.start..self.end]
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.start == self.end
+  }
+
+  pub fn peek(&self) -> Option<char> {
+    self.rest().chars().next()
+  }
+
+  pub fn bump(&mut self) -> Option<char> {
+    let c = self.peek()?;
+    self.start += c.len_utf8();
+    Some(c)
+  }
+
+  pub fn eat(&mut self, c: char) -> bool {
+    let matches = self.peek() == Some(c);
+    if matches {
+      self.start += c.len_utf8();
+    }
+    matches
+  }
+
+  pub fn expect(&mut self, c: char) -> Result<(), ParseError> {
+    if self.eat(c) {
+      Ok(())
+    } else {
+      Err(self.error(format!("{c:?}")))
+    }
+  }
+
+  pub fn end(&self) -> Result<(), ParseError> {
+    if self.is_empty() {
+      Ok(())
+    } else {
+      Err(self.error("end of line"))
+    }
+  }
+
+  pub fn skip_whitespace(&mut self) {
+    let rest = self.rest();
+    self.start += rest.len() - rest.trim_start().len();
+  }
+
+  #[must_use]
+  pub fn trim(&self) -> Self {
+    self.sub(self.rest().trim())
+  }
+
+  /// Splits off everything before the next `c`, leaving the cursor after it.
+  pub fn until(&mut self, c: char) -> Result<Self, ParseError> {
+    let Some(index) = self.rest().find(c) else {
+      return Err(self.at_end().error(format!("{c:?}")));
+    };
+    let before = Self {
+      end: self.start + index,
+      ..*self
+    };
+    self.start += index + c.len_utf8();
+    Ok(before)
+  }
+
+  #[allow(clippy::cast_possible_truncation)]
+  pub fn digit(&mut self, radix: u32) -> Result<u8, ParseError> {
+    let digit = self.peek().and_then(|c| c.to_digit(radix));
+    let digit = digit.ok_or_else(|| self.error("digit"))?;
+    self.bump();
+    Ok(digit as u8)
+  }
+
+  /// Parses the run of ASCII digits at the start of the cursor.
+  pub fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+    let rest = self.rest();
+    let len = rest
+      .find(|c: char| !c.is_ascii_digit())
+      .unwrap_or(rest.len());
+    let token = Self {
+      end: self.start + len,
+      ..*self
+    };
+    let value = token.parse("number")?;
+    self.start += len;
+    Ok(value)
+  }
+
+  /// Parses a run of digits at the start of the cursor, after an optional
+  /// minus sign.
+  pub fn signed_number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+    let rest = self.rest();
+    let sign = usize::from(rest.starts_with('-'));
+    let len = rest[sign..]
+      .find(|c: char| !c.is_ascii_digit())
+      .map_or(rest.len(), |len| sign + len);
+    let token = Self {
+      end: self.start + len,
+      ..*self
+    };
+    let value = token.parse("number")?;
+    self.start += len;
+    Ok(value)
+  }
+
+  /// Parses the run of digits in `radix` at the start of the cursor.
+  pub fn number_in(&mut self, radix: u32) -> Result<u128, ParseError> {
+    let rest = self.rest();
+    let len = rest
+      .find(|c: char| !c.is_digit(radix))
+      .unwrap_or(rest.len());
+    let token = Self {
+      end: self.start + len,
+      ..*self
+    };
+    let value = u128::from_str_radix(token.rest(), radix).map_err(|_| {
+      match token.rest() {
+        "" => token.error("number"),
+        digits => token.error_found("number", format!("{digits:?}")),
+      }
+    })?;
+    self.start += len;
+    Ok(value)
+  }
+
+  /// Parses the whole cursor as a single token.
+  pub fn parse<T: FromStr>(&self, expected: &str) -> Result<T, ParseError> {
+    self.rest().parse().map_err(|_| match self.rest() {
+      "" => self.error(expected),
+      token => self.error_found(expected, format!("{token:?}")),
+    })
+  }
+
+  pub fn split(&self, c: char) -> impl Iterator<Item = Self> + use<'a> {
+    let cursor = *self;
+    self.rest().split(c).map(move |s| cursor.sub(s))
+  }
+
+  pub fn split_whitespace(&self) -> impl Iterator<Item = Self> + use<'a> {
+    let cursor = *self;
+    self.rest().split_whitespace().map(move |s| cursor.sub(s))
+  }
+
+  pub fn lines(&self) -> Lines<'a> {
+    Lines {
+      cursor: *self,
+      inner: self.rest().lines(),
+    }
+  }
+
+  #[must_use]
+  pub fn at_end(&self) -> Self {
+    Self {
+      start: self.end,
+      ..*self
+    }
+  }
+
+  fn position(&self) -> (usize, usize) {
+    let before = &self.src[..self.start];
+    let line = 1 + before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    let column = 1 + before[line_start..].chars().count();
+    (line, column)
+  }
+
+  pub fn error(&self, expected: impl Into<String>) -> ParseError {
+    let found = match self.src[self.start..].chars().next() {
+      None => "end of input".into(),
+      Some('\n' | '\r') => "end of line".into(),
+      Some(c) => format!("{c:?}"),
+    };
+    self.error_found(expected, found)
+  }
+
+  fn error_found(
+    &self,
+    expected: impl Into<String>,
+    found: String,
+  ) -> ParseError {
+    let (line, column) = self.position();
+    ParseError {
+      day: self.day,
+      line,
+      column,
+      expected: expected.into(),
+      found,
+    }
+  }
+}
+
+/// The lines of a cursor, each as a cursor of its own.
+#[derive(Debug, Clone)]
+pub struct Lines<'a> {
+  cursor: Cursor<'a>,
+  inner: std::str::Lines<'a>,
+}
+
+impl<'a> Lines<'a> {
+  /// Returns the next line, or an error if the input has run out.
+  pub fn expect(&mut self, expected: &str) -> Result<Cursor<'a>, ParseError> {
+    self
+      .next()
+      .ok_or_else(|| self.cursor.at_end().error(expected))
+  }
+
+  pub fn expect_back(
+    &mut self,
+    expected: &str,
+  ) -> Result<Cursor<'a>, ParseError> {
+    self
+      .next_back()
+      .ok_or_else(|| self.cursor.at_end().error(expected))
+  }
+}
+
+impl<'a> Iterator for Lines<'a> {
+  type Item = Cursor<'a>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.inner.next().map(|line| self.cursor.sub(line))
+  }
+}
+
+impl DoubleEndedIterator for Lines<'_> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.inner.next_back().map(|line| self.cursor.sub(line))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reports_line_and_column() {
+    let mut lines = Cursor::new(10, "[.#] (0) {1}\n[#.] (1) {2").lines();
+    lines.next();
+    let mut line = lines.next().unwrap();
+    line.until('{').unwrap();
+    line.number::<u16>().unwrap();
+    let err = line.expect('}').unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "day10 line 2 col 12: expected '}', found end of input"
+    );
+  }
+
+  #[test]
+  fn reports_found_token() {
+    let cursor = Cursor::new(1, "R10\nLx5\nR99999999999999999999\n");
+    let mut lines = cursor.lines().skip(1);
+
+    let mut line = lines.next().unwrap();
+    line.bump();
+    let err = line.number::<u32>().unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "day01 line 2 col 2: expected number, found 'x'"
+    );
+
+    let mut line = lines.next().unwrap();
+    line.bump();
+    let err = line.number::<u64>().unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "day01 line 3 col 2: expected number, found \"99999999999999999999\""
+    );
+  }
+}
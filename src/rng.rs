@@ -0,0 +1,112 @@
+use std::ops::RangeInclusive;
+
+/// An integer type that [`Rng::range`] can sample.
+pub trait Uniform: Copy {
+  fn to_u64(self) -> u64;
+  fn from_u64(value: u64) -> Self;
+}
+
+macro_rules! impl_uniform {
+  ($($ty:ty),*) => {
+    $(
+      impl Uniform for $ty {
+        fn to_u64(self) -> u64 {
+          u64::try_from(self).expect("fits in u64")
+        }
+
+        fn from_u64(value: u64) -> Self {
+          <$ty>::try_from(value).expect("sampled within range")
+        }
+      }
+    )*
+  };
+}
+
+impl_uniform!(u8, u16, u32, u64, usize);
+
+/// A small deterministic generator (`SplitMix64`), so that generated inputs can
+/// be reproduced from their seed on any platform.
+#[derive(Debug, Clone)]
+pub struct Rng {
+  state: u64,
+}
+
+impl Rng {
+  pub fn new(seed: u64) -> Self {
+    Self { state: seed }
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+  }
+
+  /// A uniform value below `bound`, which must not be zero.
+  pub fn below(&mut self, bound: u64) -> u64 {
+    assert!(bound != 0, "empty range");
+    // Rejecting the top of the range avoids modulo bias.
+    let zone = u64::MAX - u64::MAX % bound;
+    loop {
+      let value = self.next_u64();
+      if value < zone {
+        return value % bound;
+      }
+    }
+  }
+
+  pub fn range<T: Uniform>(&mut self, range: RangeInclusive<T>) -> T {
+    let (start, end) = (range.start().to_u64(), range.end().to_u64());
+    assert!(start <= end, "empty range");
+    let value = match (end - start).checked_add(1) {
+      Some(len) => start + self.below(len),
+      None => self.next_u64(),
+    };
+    T::from_u64(value)
+  }
+
+  /// True with probability `numerator / denominator`.
+  pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+    self.below(denominator) < numerator
+  }
+
+  pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+    &items[self.range(0..=items.len() - 1)]
+  }
+
+  pub fn shuffle<T>(&mut self, items: &mut [T]) {
+    for i in (1..items.len()).rev() {
+      items.swap(i, self.range(0..=i));
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn is_deterministic() {
+    let mut a = Rng::new(7);
+    let mut b = Rng::new(7);
+    for _ in 0..100 {
+      assert_eq!(a.next_u64(), b.next_u64());
+    }
+    assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+  }
+
+  #[test]
+  fn stays_in_range() {
+    let mut rng = Rng::new(1);
+    let mut seen = [false; 6];
+    for _ in 0..1000 {
+      let value: usize = rng.range(3..=8);
+      seen[value - 3] = true;
+    }
+    assert!(seen.iter().all(|&seen| seen));
+    assert_eq!(rng.range(5u8..=5), 5);
+    rng.range(0..=u64::MAX);
+  }
+}
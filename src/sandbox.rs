@@ -0,0 +1,159 @@
+use std::{
+  any::Any,
+  fmt,
+  io::Read,
+  panic::{self, AssertUnwindSafe},
+  process::{Command, Output, Stdio},
+  sync::mpsc,
+  thread::{self, JoinHandle},
+  time::{Duration, Instant},
+};
+
+/// The message a panic was raised with, if it had one.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+  payload
+    .downcast_ref::<&str>()
+    .map(ToString::to_string)
+    .or_else(|| payload.downcast_ref::<String>().cloned())
+    .unwrap_or_default()
+}
+
+/// How a guarded run failed to produce a result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+  Panicked(String),
+  TimedOut(Duration),
+  /// A child process died without reporting a result, as it does when a
+  /// thread overflows its stack.
+  Crashed(String),
+}
+
+impl fmt::Display for Failure {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Failure::Panicked(message) => write!(f, "panicked: {message}"),
+      Failure::TimedOut(limit) => write!(f, "took longer than {limit:?}"),
+      Failure::Crashed(message) => write!(f, "crashed: {message}"),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+  pub deadline: Duration,
+  /// The stack for the thread doing the work, in bytes.
+  pub stack_size: usize,
+}
+
+impl Default for Limits {
+  /// Ten seconds, and the eight megabytes a main thread usually gets.
+  fn default() -> Self {
+    Self {
+      deadline: Duration::from_secs(10),
+      stack_size: 8 << 20,
+    }
+  }
+}
+
+/// Runs `f` on a thread of its own, catching any panic. A thread that misses
+/// the deadline is abandoned, although it keeps running until the process
+/// exits. Overflowing the stack still aborts the whole process.
+pub fn run<T: Send + 'static>(
+  name: String,
+  limits: Limits,
+  f: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, Failure> {
+  let (sender, receiver) = mpsc::channel();
+
+  thread::Builder::new()
+    .name(name)
+    .stack_size(limits.stack_size)
+    .spawn(move || {
+      let result = panic::catch_unwind(AssertUnwindSafe(f));
+      sender
+        .send(result.map_err(|payload| panic_message(&*payload)))
+        .ok();
+    })
+    .map_err(|err| Failure::Crashed(err.to_string()))?;
+
+  match receiver.recv_timeout(limits.deadline) {
+    Ok(Ok(value)) => Ok(value),
+    Ok(Err(message)) => Err(Failure::Panicked(message)),
+    Err(_) => Err(Failure::TimedOut(limits.deadline)),
+  }
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<Vec<u8>> {
+  thread::spawn(move || {
+    let mut buf = Vec::new();
+    if let Some(mut pipe) = pipe {
+      pipe.read_to_end(&mut buf).ok();
+    }
+    buf
+  })
+}
+
+/// Runs a command with its output captured, killing it at the deadline. The
+/// output comes back whatever the exit status, for the caller to judge.
+pub fn run_process(
+  command: &mut Command,
+  deadline: Duration,
+) -> Result<Output, Failure> {
+  let crashed = |err: std::io::Error| Failure::Crashed(err.to_string());
+
+  let mut child = command
+    .stdin(Stdio::null())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .map_err(crashed)?;
+
+  // Read both pipes as the child writes, so that it never blocks on a full
+  // one while we wait for it to exit.
+  let stdout = drain(child.stdout.take());
+  let stderr = drain(child.stderr.take());
+
+  let start = Instant::now();
+  let status = loop {
+    if let Some(status) = child.try_wait().map_err(crashed)? {
+      break status;
+    }
+    if start.elapsed() >= deadline {
+      child.kill().ok();
+      child.wait().ok();
+      return Err(Failure::TimedOut(deadline));
+    }
+    thread::sleep(Duration::from_millis(1));
+  };
+
+  Ok(Output {
+    status,
+    stdout: stdout.join().unwrap_or_default(),
+    stderr: stderr.join().unwrap_or_default(),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn catches_panics_and_deadlines() {
+    let limits = Limits {
+      deadline: Duration::from_millis(100),
+      ..Limits::default()
+    };
+    let name = || "sandbox test".to_string();
+
+    assert_eq!(run(name(), limits, || 42), Ok(42));
+
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let panicked = run(name(), limits, || panic!("boom"));
+    panic::set_hook(hook);
+    assert_eq!(panicked, Err(Failure::Panicked("boom".into())));
+
+    let slow = run(name(), limits, || thread::sleep(Duration::from_secs(1)));
+    assert_eq!(slow, Err(Failure::TimedOut(limits.deadline)));
+  }
+}
@@ -0,0 +1,162 @@
+use std::{
+  fs, io,
+  path::{Path, PathBuf},
+};
+
+use crate::input::Inputs;
+
+fn invalid(path: &Path, message: &str) -> io::Error {
+  io::Error::new(
+    io::ErrorKind::InvalidData,
+    format!("{}: {message}", path.display()),
+  )
+}
+
+/// Inserts `line` among the lines that `key` numbers, keeping them sorted by
+/// that number.
+fn insert_sorted(
+  text: &str,
+  line: &str,
+  key: impl Fn(&str) -> Option<u8>,
+) -> Option<String> {
+  let number = key(line)?;
+  let mut lines: Vec<&str> = text.lines().collect();
+
+  let numbered: Vec<usize> = (0..lines.len())
+    .filter(|&i| key(lines[i]).is_some())
+    .collect();
+  let index = numbered
+    .iter()
+    .find(|&&i| key(lines[i]) > Some(number))
+    .copied()
+    .unwrap_or(numbered.last()? + 1);
+
+  lines.insert(index, line);
+
+  let mut text = lines.join("\n");
+  text.push('\n');
+  Some(text)
+}
+
+fn module_number(line: &str) -> Option<u8> {
+  line
+    .strip_prefix("pub mod day")?
+    .strip_suffix(';')?
+    .parse()
+    .ok()
+}
+
+fn registry_number(line: &str) -> Option<u8> {
+  line
+    .trim_start()
+    .strip_prefix("Day::of::<day")?
+    .strip_suffix("::Puzzle>(),")?
+    .parse()
+    .ok()
+}
+
+/// Starts a new day from the `day00` template: its module, an empty example,
+/// an empty input, and its entries in `lib.rs`. Returns the files it touched.
+pub fn scaffold(
+  root: &Path,
+  inputs: &Inputs,
+  day: u8,
+) -> io::Result<Vec<PathBuf>> {
+  let src = root.join("src");
+  let dir = src.join(format!("day{day:02}"));
+  if dir.exists() {
+    return Err(io::Error::new(
+      io::ErrorKind::AlreadyExists,
+      format!("{} already exists", dir.display()),
+    ));
+  }
+
+  let template_path = src.join("day00").join("mod.rs");
+  let template = fs::read_to_string(&template_path)?;
+  let module = template.replacen(
+    "const DAY: u8 = 0;",
+    &format!("const DAY: u8 = {day};"),
+    1,
+  );
+  if module == template {
+    return Err(invalid(
+      &template_path,
+      "no `const DAY: u8 = 0;` to replace",
+    ));
+  }
+
+  let lib_path = src.join("lib.rs");
+  let lib = fs::read_to_string(&lib_path)?;
+  let lib =
+    insert_sorted(&lib, &format!("pub mod day{day:02};"), module_number)
+      .ok_or_else(|| invalid(&lib_path, "no `pub mod dayNN;` lines"))?;
+  let lib = insert_sorted(
+    &lib,
+    &format!("  Day::of::<day{day:02}::Puzzle>(),"),
+    registry_number,
+  )
+  .ok_or_else(|| invalid(&lib_path, "no `Day::of::<dayNN::Puzzle>()` lines"))?;
+
+  let module_path = dir.join("mod.rs");
+  let example_path = dir.join("input_test");
+  let input_path = inputs.path(day, "input");
+
+  fs::create_dir_all(&dir)?;
+  fs::write(&module_path, module)?;
+  fs::write(&example_path, "")?;
+  if !input_path.exists() {
+    fs::create_dir_all(input_path.parent().expect("path has a parent"))?;
+    fs::write(&input_path, "")?;
+  }
+  fs::write(&lib_path, lib)?;
+
+  Ok(vec![module_path, example_path, input_path, lib_path])
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn scaffolds_a_day() {
+    let root =
+      std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+    fs::remove_dir_all(&root).ok();
+    fs::create_dir_all(root.join("src/day00")).unwrap();
+    fs::write(root.join("src/day00/mod.rs"), include_str!("day00/mod.rs"))
+      .unwrap();
+    fs::write(
+      root.join("src/lib.rs"),
+      "pub mod day00;\npub mod day01;\npub mod day05;\n\npub mod parse;\n\n\
+       pub static DAYS: &[Day] = &[\n  Day::of::<day01::Puzzle>(),\n  \
+       Day::of::<day05::Puzzle>(),\n];\n",
+    )
+    .unwrap();
+    let inputs = Inputs::new(root.join("inputs"));
+
+    scaffold(&root, &inputs, 3).unwrap();
+
+    assert_eq!(
+      fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+      "pub mod day00;\npub mod day01;\npub mod day03;\npub mod day05;\n\n\
+       pub mod parse;\n\npub static DAYS: &[Day] = &[\n  \
+       Day::of::<day01::Puzzle>(),\n  Day::of::<day03::Puzzle>(),\n  \
+       Day::of::<day05::Puzzle>(),\n];\n"
+    );
+    assert!(
+      fs::read_to_string(root.join("src/day03/mod.rs"))
+        .unwrap()
+        .contains("const DAY: u8 = 3;")
+    );
+    assert_eq!(
+      fs::read_to_string(root.join("src/day03/input_test")).unwrap(),
+      ""
+    );
+    assert_eq!(fs::read_to_string(inputs.path(3, "input")).unwrap(), "");
+
+    let err = scaffold(&root, &inputs, 3).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+
+    fs::remove_dir_all(&root).unwrap();
+  }
+}
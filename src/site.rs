@@ -0,0 +1,418 @@
+use std::{
+  env, fmt, fs, io,
+  path::{Path, PathBuf},
+  time::Duration,
+};
+
+use crate::{
+  Part,
+  http::{Client, Request, Response, client_for},
+  input::Inputs,
+};
+
+pub const YEAR: u16 = 2025;
+
+/// The event has twelve puzzles this year.
+pub const LAST_DAY: u8 = 12;
+
+/// Overrides where the site is reached, e.g. a local stand-in server.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Holds the `session` cookie, taking precedence over the config file.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// Identifies this tool to the site, as its operators ask automated clients
+/// to do.
+pub fn user_agent() -> String {
+  format!(
+    "{}/{} (+{} by {})",
+    env!("CARGO_PKG_NAME"),
+    env!("CARGO_PKG_VERSION"),
+    env!("CARGO_PKG_REPOSITORY"),
+    env!("CARGO_PKG_AUTHORS"),
+  )
+}
+
+/// `$XDG_CONFIG_HOME/aoc/session`, or `~/.config/aoc/session`.
+pub fn session_path() -> Option<PathBuf> {
+  let config = env::var_os("XDG_CONFIG_HOME")
+    .map(PathBuf::from)
+    .or_else(|| Some(Path::new(&env::var_os("HOME")?).join(".config")))?;
+  Some(config.join("aoc").join("session"))
+}
+
+#[derive(Debug)]
+pub enum Error {
+  NoSession,
+  Io(io::Error),
+  Status(Response),
+  /// A page the site served that we could not make sense of.
+  Unexpected(String),
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Error::NoSession => write!(
+        f,
+        "no session token: set {SESSION_ENV} or write it to {}",
+        session_path().map_or("~/.config/aoc/session".into(), |path| path
+          .display()
+          .to_string())
+      ),
+      Error::Io(err) => write!(f, "{err}"),
+      Error::Status(response) => write!(
+        f,
+        "server responded with {}: {}",
+        response.status,
+        response.body.lines().next().unwrap_or_default()
+      ),
+      Error::Unexpected(text) => write!(f, "unexpected response: {text}"),
+    }
+  }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+  fn from(err: io::Error) -> Self {
+    Error::Io(err)
+  }
+}
+
+/// The Advent of Code website, reached through a swappable client.
+pub struct Site {
+  base_url: String,
+  session: Option<String>,
+  client: Box<dyn Client>,
+}
+
+impl Site {
+  pub fn new(
+    base_url: impl Into<String>,
+    session: Option<String>,
+    client: Box<dyn Client>,
+  ) -> Self {
+    Self {
+      base_url: base_url.into().trim_end_matches('/').to_owned(),
+      session,
+      client,
+    }
+  }
+
+  /// Reads the base url and session token from the environment, falling back
+  /// to the session config file.
+  pub fn from_env() -> Self {
+    let base_url =
+      env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+    let session = env::var(SESSION_ENV)
+      .ok()
+      .or_else(|| fs::read_to_string(session_path()?).ok())
+      .map(|session| session.trim().to_owned())
+      .filter(|session| !session.is_empty());
+    let client = client_for(&base_url);
+    Self::new(base_url, session, client)
+  }
+
+  /// Sends a request for a path under this year's event.
+  pub(crate) fn send(
+    &self,
+    method: &'static str,
+    path: &str,
+    body: Option<String>,
+  ) -> Result<String, Error> {
+    let session = self.session.as_ref().ok_or(Error::NoSession)?;
+
+    let mut headers = vec![
+      ("User-Agent".into(), user_agent()),
+      ("Cookie".into(), format!("session={session}")),
+    ];
+    if body.is_some() {
+      headers.push((
+        "Content-Type".into(),
+        "application/x-www-form-urlencoded".into(),
+      ));
+    }
+
+    let response = self.client.send(&Request {
+      method,
+      url: format!("{}/{YEAR}/{path}", self.base_url),
+      headers,
+      body,
+    })?;
+
+    if response.status == 200 {
+      Ok(response.body)
+    } else {
+      Err(Error::Status(response))
+    }
+  }
+
+  pub fn input(&self, day: u8) -> Result<String, Error> {
+    self.send("GET", &format!("day/{day}/input"), None)
+  }
+
+  pub fn answer(
+    &self,
+    day: u8,
+    part: Part,
+    answer: &str,
+  ) -> Result<Verdict, Error> {
+    let body = format!("level={}&answer={}", part.number(), encode(answer));
+    let page = self.send("POST", &format!("day/{day}/answer"), Some(body))?;
+    Verdict::parse(&page).ok_or_else(|| Error::Unexpected(article(&page)))
+  }
+}
+
+/// Percent-encodes a form value.
+fn encode(value: &str) -> String {
+  value
+    .bytes()
+    .map(|byte| match byte {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+        char::from(byte).to_string()
+      }
+      _ => format!("%{byte:02X}"),
+    })
+    .collect()
+}
+
+/// The text of the page's `<article>`, where the site puts its message.
+fn article(page: &str) -> String {
+  let start = page.find("<article").unwrap_or(0);
+  let end = page[start..]
+    .find("</article>")
+    .map_or(page.len(), |end| start + end);
+
+  let mut text = String::new();
+  let mut in_tag = false;
+  for c in page[start..end].chars() {
+    match c {
+      '<' => in_tag = true,
+      '>' => in_tag = false,
+      c if !in_tag => text.push(c),
+      _ => {}
+    }
+  }
+  text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// How the site judged a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+  Correct,
+  TooHigh,
+  TooLow,
+  /// Wrong, without a hint in either direction.
+  Wrong,
+  /// Not judged, because the last guess was too recent.
+  RateLimited(Duration),
+  /// Not judged, because the part is already solved or still locked.
+  WrongLevel,
+}
+
+impl Verdict {
+  pub fn parse(page: &str) -> Option<Self> {
+    let text = article(page);
+    let verdict = if text.contains("That's the right answer") {
+      Verdict::Correct
+    } else if text.contains("That's not the right answer") {
+      if text.contains("your answer is too high") {
+        Verdict::TooHigh
+      } else if text.contains("your answer is too low") {
+        Verdict::TooLow
+      } else {
+        Verdict::Wrong
+      }
+    } else if text.contains("You gave an answer too recently") {
+      Verdict::RateLimited(parse_wait(&text).unwrap_or_default())
+    } else if text.contains("You don't seem to be solving the right level") {
+      Verdict::WrongLevel
+    } else {
+      return None;
+    };
+    Some(verdict)
+  }
+
+  /// Whether the site actually judged the answer.
+  pub fn is_judged(self) -> bool {
+    !matches!(self, Verdict::RateLimited(_) | Verdict::WrongLevel)
+  }
+}
+
+/// Parses the wait out of e.g. "You have 1m 23s left to wait."
+fn parse_wait(text: &str) -> Option<Duration> {
+  let start = text.find("You have ")? + "You have ".len();
+  let end = start + text[start..].find(" left to wait")?;
+
+  let mut secs = 0;
+  for token in text[start..end].split_whitespace() {
+    let (value, unit) = token.split_at(token.len().checked_sub(1)?);
+    let value: u64 = value.parse().ok()?;
+    secs += value
+      * match unit {
+        "h" => 3600,
+        "m" => 60,
+        "s" => 1,
+        _ => return None,
+      };
+  }
+  Some(Duration::from_secs(secs))
+}
+
+impl fmt::Display for Verdict {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Verdict::Correct => f.write_str("correct"),
+      Verdict::TooHigh => f.write_str("too high"),
+      Verdict::TooLow => f.write_str("too low"),
+      Verdict::Wrong => f.write_str("wrong"),
+      Verdict::RateLimited(wait) => {
+        write!(f, "rate limited, wait {}s", wait.as_secs())
+      }
+      Verdict::WrongLevel => f.write_str("already solved or not unlocked"),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+  Cached(PathBuf),
+  Downloaded(PathBuf),
+}
+
+/// Downloads a day's input into the inputs directory, unless it is already
+/// there. An empty file, as left by `aoc new`, does not count.
+pub fn fetch(site: &Site, inputs: &Inputs, day: u8) -> Result<Fetched, Error> {
+  let path = inputs.path(day, "input");
+  if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+    return Ok(Fetched::Cached(path));
+  }
+
+  let input = site.input(day)?;
+
+  // Write next to the destination first, so that an interrupted download
+  // never leaves a truncated input that would be mistaken for a cached one.
+  fs::create_dir_all(path.parent().expect("input path has a parent"))?;
+  let partial = path.with_extension("partial");
+  fs::write(&partial, input)?;
+  fs::rename(&partial, &path)?;
+
+  Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::http::{TcpClient, mock::MockServer};
+
+  fn temp_inputs(name: &str) -> Inputs {
+    let dir = env::temp_dir()
+      .join(format!("aoc-{name}-{}", std::process::id()))
+      .join("inputs");
+    fs::remove_dir_all(&dir).ok();
+    Inputs::new(dir)
+  }
+
+  #[test]
+  fn fetches_and_caches_inputs() {
+    let server = MockServer::start(vec![(200, "1\n2\n3\n".into())]);
+    let site = Site::new(&server.url, Some("abc".into()), Box::new(TcpClient));
+    let inputs = temp_inputs("fetch");
+    let path = inputs.path(3, "input");
+
+    assert_eq!(
+      fetch(&site, &inputs, 3).unwrap(),
+      Fetched::Downloaded(path.clone())
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n3\n");
+    assert_eq!(fetch(&site, &inputs, 3).unwrap(), Fetched::Cached(path));
+
+    let received = server.received();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].path, "/2025/day/3/input");
+    assert_eq!(received[0].header("cookie"), Some("session=abc"));
+    assert_eq!(received[0].header("user-agent"), Some(&*user_agent()));
+
+    fs::remove_dir_all(inputs.dir()).unwrap();
+  }
+
+  #[test]
+  fn does_not_cache_errors() {
+    let server = MockServer::start(vec![(
+      400,
+      "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+        .into(),
+    )]);
+    let site = Site::new(&server.url, Some("abc".into()), Box::new(TcpClient));
+    let inputs = temp_inputs("fetch-error");
+
+    let err = fetch(&site, &inputs, 1).unwrap_err();
+    assert!(matches!(err, Error::Status(Response { status: 400, .. })));
+    assert!(!inputs.path(1, "input").exists());
+  }
+
+  #[test]
+  fn parses_verdicts() {
+    let page =
+      |text| format!("<main>\n<article><p>{text}</p></article>\n</main>");
+
+    assert_eq!(
+      Verdict::parse(&page(
+        "That's the right answer!  You are <span>one gold star</span> closer."
+      )),
+      Some(Verdict::Correct)
+    );
+    assert_eq!(
+      Verdict::parse(&page(
+        "That's not the right answer; your answer is too high.  If you're \
+         stuck, make sure you're using the full input data."
+      )),
+      Some(Verdict::TooHigh)
+    );
+    assert_eq!(
+      Verdict::parse(&page(
+        "That's not the right answer; your answer is too low."
+      )),
+      Some(Verdict::TooLow)
+    );
+    assert_eq!(
+      Verdict::parse(&page(
+        "You gave an answer too recently; you have to wait after submitting \
+         an answer before trying again.  You have 1m 23s left to wait."
+      )),
+      Some(Verdict::RateLimited(Duration::from_secs(83)))
+    );
+    assert_eq!(
+      Verdict::parse(&page(
+        "You don't seem to be solving the right level.  Did you already \
+         complete it?"
+      )),
+      Some(Verdict::WrongLevel)
+    );
+    assert_eq!(Verdict::parse(&page("Something else entirely.")), None);
+  }
+
+  #[test]
+  fn posts_answers() {
+    let server = MockServer::start(vec![(
+      200,
+      "<article><p>That's the right answer!</p></article>".into(),
+    )]);
+    let site = Site::new(&server.url, Some("abc".into()), Box::new(TcpClient));
+
+    assert_eq!(site.answer(5, Part::Two, "42").unwrap(), Verdict::Correct);
+
+    let received = server.received();
+    assert_eq!(received[0].method, "POST");
+    assert_eq!(received[0].path, "/2025/day/5/answer");
+    assert_eq!(received[0].body, "level=2&answer=42");
+  }
+
+  #[test]
+  fn requires_a_session() {
+    let site = Site::new("http://127.0.0.1:1", None, Box::new(TcpClient));
+    assert!(matches!(site.input(1), Err(Error::NoSession)));
+  }
+}
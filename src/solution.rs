@@ -0,0 +1,245 @@
+use std::fmt;
+
+use crate::{
+  ParseError,
+  params::{Param, Params},
+  rng::Rng,
+  trace::Trace,
+};
+
+/// One of the two halves of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+  One,
+  Two,
+}
+
+impl Part {
+  pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+  pub const fn number(self) -> u8 {
+    match self {
+      Part::One => 1,
+      Part::Two => 2,
+    }
+  }
+}
+
+impl TryFrom<u8> for Part {
+  type Error = u8;
+
+  fn try_from(value: u8) -> Result<Self, Self::Error> {
+    match value {
+      1 => Ok(Part::One),
+      2 => Ok(Part::Two),
+      _ => Err(value),
+    }
+  }
+}
+
+impl fmt::Display for Part {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.number().fmt(f)
+  }
+}
+
+/// The answer to a puzzle part, keeping the integer type it was computed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Answer {
+  U16(u16),
+  U64(u64),
+  U128(u128),
+  Usize(usize),
+}
+
+macro_rules! impl_answer_from {
+  ($($variant:ident($ty:ty)),*) => {
+    $(
+      impl From<$ty> for Answer {
+        fn from(value: $ty) -> Self {
+          Answer::$variant(value)
+        }
+      }
+    )*
+  };
+}
+
+impl_answer_from!(U16(u16), U64(u64), U128(u128), Usize(usize));
+
+impl Answer {
+  /// The integer type the answer was computed in.
+  pub fn type_name(self) -> &'static str {
+    match self {
+      Answer::U16(_) => "u16",
+      Answer::U64(_) => "u64",
+      Answer::U128(_) => "u128",
+      Answer::Usize(_) => "usize",
+    }
+  }
+}
+
+impl fmt::Display for Answer {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Answer::U16(value) => value.fmt(f),
+      Answer::U64(value) => value.fmt(f),
+      Answer::U128(value) => value.fmt(f),
+      Answer::Usize(value) => value.fmt(f),
+    }
+  }
+}
+
+/// A solved puzzle: parse the input once, then answer either part from it.
+pub trait Solution {
+  const DAY: u8;
+
+  type Input<'a>;
+
+  /// The parameters `parse` reads, with their defaults.
+  const PARAMS: &'static [Param] = &[];
+
+  /// The parts the puzzle has, which `part2` must agree with.
+  const PARTS: &'static [Part] = &Part::ALL;
+
+  fn parse<'a>(
+    input: &'a str,
+    params: &Params,
+  ) -> Result<Self::Input<'a>, ParseError>;
+
+  /// A random, well-formed input to parse with `params`. `size` scales it,
+  /// roughly as the number of lines or items, so that small sizes stay easy
+  /// to check by hand.
+  fn generate(rng: &mut Rng, size: usize, params: &Params) -> String;
+
+  /// Parameters to generate inputs for, so that generated tests also cover
+  /// values other than the defaults.
+  fn generate_params(_rng: &mut Rng) -> Params {
+    Params::defaults(Self::PARAMS)
+  }
+
+  fn part1(input: &Self::Input<'_>) -> Answer;
+
+  /// The final day only has one part.
+  fn part2(_input: &Self::Input<'_>) -> Option<Answer> {
+    None
+  }
+
+  /// A slow but straightforward answer to check the fast one against, for
+  /// inputs small enough that it finishes.
+  fn reference(_input: &Self::Input<'_>, _part: Part) -> Option<Answer> {
+    None
+  }
+
+  /// The steps behind the answers, for days where it helps to see them.
+  fn trace(_input: &Self::Input<'_>) -> Option<Trace> {
+    None
+  }
+}
+
+/// A parsed input with its solution type erased.
+pub trait Solve {
+  fn solve(&self, part: Part) -> Option<Answer>;
+
+  /// See [`Solution::reference`].
+  fn reference(&self, part: Part) -> Option<Answer>;
+
+  /// See [`Solution::trace`].
+  fn trace(&self) -> Option<Trace>;
+}
+
+struct Parsed<'a, S: Solution>(S::Input<'a>);
+
+impl<S: Solution> Solve for Parsed<'_, S> {
+  fn solve(&self, part: Part) -> Option<Answer> {
+    match part {
+      Part::One => Some(S::part1(&self.0)),
+      Part::Two => S::part2(&self.0),
+    }
+  }
+
+  fn reference(&self, part: Part) -> Option<Answer> {
+    S::reference(&self.0, part)
+  }
+
+  fn trace(&self) -> Option<Trace> {
+    S::trace(&self.0)
+  }
+}
+
+fn parse<'a, S: Solution + 'static>(
+  input: &'a str,
+  params: &Params,
+) -> Result<Box<dyn Solve + 'a>, ParseError> {
+  Ok(Box::new(Parsed::<S>(S::parse(input, params)?)))
+}
+
+type ParseFn =
+  for<'a> fn(&'a str, &Params) -> Result<Box<dyn Solve + 'a>, ParseError>;
+
+/// A registry entry, letting days be iterated without naming their types.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+  pub number: u8,
+  pub params: &'static [Param],
+  pub parts: &'static [Part],
+  parse: ParseFn,
+  generate: fn(&mut Rng, usize, &Params) -> String,
+  generate_params: fn(&mut Rng) -> Params,
+}
+
+impl Day {
+  pub const fn of<S: Solution + 'static>() -> Self {
+    Self {
+      number: S::DAY,
+      params: S::PARAMS,
+      parts: S::PARTS,
+      parse: parse::<S>,
+      generate: S::generate,
+      generate_params: S::generate_params,
+    }
+  }
+
+  pub fn defaults(&self) -> Params {
+    Params::defaults(self.params)
+  }
+
+  /// See [`Params::resolve`].
+  pub fn resolve<'o>(
+    &self,
+    overrides: impl IntoIterator<Item = (&'o str, &'o str)>,
+  ) -> Result<Params, String> {
+    Params::resolve(self.params, overrides)
+      .map_err(|err| format!("day {}: {err}", self.number))
+  }
+
+  pub fn parse<'a>(
+    &self,
+    input: &'a str,
+    params: &Params,
+  ) -> Result<Box<dyn Solve + 'a>, ParseError> {
+    (self.parse)(input, params)
+  }
+
+  pub fn generate(
+    &self,
+    rng: &mut Rng,
+    size: usize,
+    params: &Params,
+  ) -> String {
+    (self.generate)(rng, size, params)
+  }
+
+  /// See [`Solution::generate_params`].
+  pub fn generate_params(&self, rng: &mut Rng) -> Params {
+    (self.generate_params)(rng)
+  }
+
+  pub fn solve(
+    &self,
+    input: &str,
+    part: Part,
+    params: &Params,
+  ) -> Result<Option<Answer>, ParseError> {
+    Ok(self.parse(input, params)?.solve(part))
+  }
+}
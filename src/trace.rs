@@ -0,0 +1,120 @@
+use std::fmt::Write;
+
+use crate::{
+  calendar::{Format, csv_field},
+  json::Value,
+};
+
+/// A step-by-step account of how a day reached its answers, one row per step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace {
+  pub columns: Vec<&'static str>,
+  pub rows: Vec<Vec<Value>>,
+}
+
+/// A cell as plain text, without the quotes JSON puts around strings.
+fn cell(value: &Value) -> String {
+  match value {
+    Value::String(value) => value.clone(),
+    value => value.to_string(),
+  }
+}
+
+impl Trace {
+  /// The rows as a table with right-aligned columns, a JSON array of records,
+  /// or CSV with a header line.
+  pub fn render(&self, format: Format) -> String {
+    match format {
+      Format::Text => {
+        let rows: Vec<Vec<String>> = self
+          .rows
+          .iter()
+          .map(|row| row.iter().map(cell).collect())
+          .collect();
+        let widths: Vec<usize> = self
+          .columns
+          .iter()
+          .enumerate()
+          .map(|(i, column)| {
+            rows
+              .iter()
+              .filter_map(|row| row.get(i).map(String::len))
+              .fold(column.len(), usize::max)
+          })
+          .collect();
+
+        let mut out = String::new();
+        let header = self.columns.iter().map(|column| (*column).to_owned());
+        for row in [header.collect()].iter().chain(&rows) {
+          let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:>width$}"))
+            .collect();
+          writeln!(out, "{}", line.join("  ")).unwrap();
+        }
+        out
+      }
+      Format::Json => {
+        let records = self
+          .rows
+          .iter()
+          .map(|row| {
+            Value::Object(
+              self
+                .columns
+                .iter()
+                .map(|column| (*column).to_owned())
+                .zip(row.iter().cloned())
+                .collect(),
+            )
+          })
+          .collect();
+        format!("{:#}\n", Value::Array(records))
+      }
+      Format::Csv => {
+        let mut out = self.columns.join(",");
+        out.push('\n');
+        for row in &self.rows {
+          let fields: Vec<_> =
+            row.iter().map(|value| csv_field(&cell(value))).collect();
+          out.push_str(&fields.join(","));
+          out.push('\n');
+        }
+        out
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn renders_formats() {
+    let trace = Trace {
+      columns: vec!["step", "note"],
+      rows: vec![
+        vec![1u64.into(), "start".into()],
+        vec![10u64.into(), "a, b".into()],
+      ],
+    };
+
+    assert_eq!(
+      trace.render(Format::Text),
+      "step   note\n   1  start\n  10   a, b\n"
+    );
+    assert_eq!(
+      trace.render(Format::Csv),
+      "step,note\n1,start\n10,\"a, b\"\n"
+    );
+    let json = Value::parse(&trace.render(Format::Json)).unwrap();
+    assert_eq!(
+      json.as_array().unwrap()[1]
+        .get("note")
+        .and_then(Value::as_str),
+      Some("a, b")
+    );
+  }
+}
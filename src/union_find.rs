@@ -0,0 +1,127 @@
+/// Disjoint sets over `0..len`, with path compression and union by size so
+/// that any sequence of operations runs in near-linear time.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+  parents: Vec<usize>,
+  sizes: Vec<usize>,
+  components: usize,
+}
+
+impl UnionFind {
+  /// Starts with every element in a component of its own.
+  pub fn new(len: usize) -> Self {
+    Self {
+      parents: (0..len).collect(),
+      sizes: vec![1; len],
+      components: len,
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.parents.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.parents.is_empty()
+  }
+
+  /// The number of disjoint components.
+  pub fn components(&self) -> usize {
+    self.components
+  }
+
+  /// The representative of the component containing `element`.
+  pub fn find(&mut self, element: usize) -> usize {
+    let mut root = element;
+    while self.parents[root] != root {
+      root = self.parents[root];
+    }
+
+    let mut element = element;
+    while self.parents[element] != root {
+      element = std::mem::replace(&mut self.parents[element], root);
+    }
+
+    root
+  }
+
+  /// Merges the components of `a` and `b`, returning whether they were apart.
+  pub fn union(&mut self, a: usize, b: usize) -> bool {
+    let (a, b) = (self.find(a), self.find(b));
+    if a == b {
+      return false;
+    }
+
+    let (large, small) = if self.sizes[a] < self.sizes[b] {
+      (b, a)
+    } else {
+      (a, b)
+    };
+    self.parents[small] = large;
+    self.sizes[large] += self.sizes[small];
+    self.components -= 1;
+
+    true
+  }
+
+  pub fn connected(&mut self, a: usize, b: usize) -> bool {
+    self.find(a) == self.find(b)
+  }
+
+  /// The size of the component containing `element`.
+  pub fn size(&mut self, element: usize) -> usize {
+    let root = self.find(element);
+    self.sizes[root]
+  }
+
+  /// The representative of each component.
+  pub fn roots(&self) -> impl Iterator<Item = usize> {
+    (0..self.len()).filter(|&element| self.parents[element] == element)
+  }
+
+  /// The size of each component.
+  pub fn sizes(&self) -> impl Iterator<Item = usize> {
+    self.roots().map(|root| self.sizes[root])
+  }
+
+  /// The members of each component, in order of their smallest member.
+  pub fn groups(&mut self) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+    let mut group_of_root = vec![usize::MAX; self.len()];
+
+    for element in 0..self.len() {
+      let root = self.find(element);
+      if group_of_root[root] == usize::MAX {
+        group_of_root[root] = groups.len();
+        groups.push(Vec::new());
+      }
+      groups[group_of_root[root]].push(element);
+    }
+
+    groups
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn merges_components() {
+    let mut sets = UnionFind::new(6);
+    assert!(sets.union(0, 1));
+    assert!(sets.union(4, 5));
+    assert!(sets.union(5, 1));
+    assert!(!sets.union(0, 4));
+
+    assert_eq!(sets.components(), 3);
+    assert!(sets.connected(0, 5));
+    assert!(!sets.connected(2, 3));
+    assert_eq!(sets.size(4), 4);
+
+    let mut sizes: Vec<_> = sets.sizes().collect();
+    sizes.sort_unstable();
+    assert_eq!(sizes, [1, 1, 4]);
+    assert_eq!(sets.groups(), [vec![0, 1, 4, 5], vec![2], vec![3]]);
+  }
+}
@@ -0,0 +1,214 @@
+//! Checks every answer in `answers.toml`, as one test per entry.
+//!
+//! The tests are generated from data, so this uses its own minimal harness
+//! that understands enough of libtest's command line for `cargo test` and
+//! `cargo nextest` to list, filter and run individual entries.
+
+use std::{
+  env,
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use advent_of_code_2025::{
+  calendar, day,
+  input::Inputs,
+  manifest::{Entry, Manifest},
+  sandbox::Limits,
+};
+
+enum Outcome {
+  Passed,
+  Failed(String),
+  Ignored(String),
+}
+
+struct Test {
+  name: String,
+  entry: Entry,
+  missing_input: Option<String>,
+}
+
+#[derive(Default, PartialEq, Eq)]
+enum Ignored {
+  #[default]
+  Skip,
+  Only,
+  Include,
+}
+
+#[derive(Default)]
+struct Args {
+  list: bool,
+  exact: bool,
+  ignored: Ignored,
+  filters: Vec<String>,
+  skip: Vec<String>,
+}
+
+impl Args {
+  fn parse() -> Self {
+    let mut args = Self::default();
+    let mut it = env::args().skip(1);
+
+    while let Some(arg) = it.next() {
+      match arg.as_str() {
+        "--list" => args.list = true,
+        "--ignored" => args.ignored = Ignored::Only,
+        "--include-ignored" => args.ignored = Ignored::Include,
+        "--exact" => args.exact = true,
+        "--skip" => args.skip.extend(it.next()),
+        "--format" | "--color" | "--test-threads" | "--logfile" => {
+          it.next();
+        }
+        _ if arg.starts_with('-') => {}
+        _ => args.filters.push(arg),
+      }
+    }
+
+    args
+  }
+
+  fn matches(&self, name: &str) -> bool {
+    let matches = |filter: &String| {
+      if self.exact {
+        name == filter
+      } else {
+        name.contains(filter.as_str())
+      }
+    };
+
+    (self.filters.is_empty() || self.filters.iter().any(matches))
+      && !self.skip.iter().any(matches)
+  }
+}
+
+fn run(entry: &Entry, manifest: &Manifest, inputs: &Inputs) -> Outcome {
+  let Some(day) = day(entry.day) else {
+    return Outcome::Failed(format!("day {} is not registered", entry.day));
+  };
+
+  let params = match day.resolve(manifest.params(entry.day, &entry.input)) {
+    Ok(params) => params,
+    Err(err) => return Outcome::Failed(err),
+  };
+
+  let input = match inputs.load(entry.day, &entry.input) {
+    Ok(input) => input,
+    Err(err) => return Outcome::Failed(err.to_string()),
+  };
+
+  let solved =
+    calendar::solve(day, &input, entry.part, &params, Limits::default());
+
+  match solved.map(|solved| solved.answer) {
+    Some(Ok(answer)) if answer.to_string() == entry.answer => Outcome::Passed,
+    Some(Ok(answer)) => {
+      Outcome::Failed(format!("expected {}, got {answer}", entry.answer))
+    }
+    Some(Err(err)) => Outcome::Failed(err),
+    None => Outcome::Failed(format!("no part {}", entry.part)),
+  }
+}
+
+fn main() -> ExitCode {
+  let args = Args::parse();
+
+  let manifest = match Manifest::load(&Manifest::default_path()) {
+    Ok(manifest) => manifest,
+    Err(err) => {
+      eprintln!("error: {err}");
+      return ExitCode::FAILURE;
+    }
+  };
+
+  let inputs = Inputs::from_env();
+
+  let tests: Vec<Test> = manifest
+    .entries()
+    .iter()
+    .map(|entry| Test {
+      name: format!("day{:02}::{}::part{}", entry.day, entry.input, entry.part),
+      entry: entry.clone(),
+      missing_input: inputs.resolve(entry.day, &entry.input).is_none().then(
+        || {
+          format!(
+            "no input at {}",
+            inputs.path(entry.day, &entry.input).display()
+          )
+        },
+      ),
+    })
+    .collect();
+
+  let total = tests.len();
+
+  let tests: Vec<Test> = tests
+    .into_iter()
+    .filter(|test| args.matches(&test.name))
+    .filter(|test| {
+      args.ignored != Ignored::Only || test.missing_input.is_some()
+    })
+    .collect();
+
+  if args.list {
+    for test in &tests {
+      println!("{}: test", test.name);
+    }
+    return ExitCode::SUCCESS;
+  }
+
+  println!("\nrunning {} tests", tests.len());
+
+  let mut failures = Vec::new();
+  let mut passed = 0;
+  let mut ignored = 0;
+
+  for test in &tests {
+    print!("test {} ... ", test.name);
+    io::stdout().flush().ok();
+
+    let outcome = match &test.missing_input {
+      Some(reason) if args.ignored == Ignored::Skip => {
+        Outcome::Ignored(reason.clone())
+      }
+      _ => run(&test.entry, &manifest, &inputs),
+    };
+
+    match outcome {
+      Outcome::Passed => {
+        passed += 1;
+        println!("ok");
+      }
+      Outcome::Ignored(reason) => {
+        ignored += 1;
+        println!("ignored, {reason}");
+      }
+      Outcome::Failed(reason) => {
+        println!("FAILED");
+        failures.push((&test.name, reason));
+      }
+    }
+  }
+
+  if !failures.is_empty() {
+    println!("\nfailures:");
+    for (name, reason) in &failures {
+      println!("    {name}: {reason}");
+    }
+  }
+
+  println!(
+    "\ntest result: {}. {passed} passed; {} failed; {ignored} ignored; \
+     0 measured; {} filtered out\n",
+    if failures.is_empty() { "ok" } else { "FAILED" },
+    failures.len(),
+    total - tests.len(),
+  );
+
+  if failures.is_empty() {
+    ExitCode::SUCCESS
+  } else {
+    ExitCode::from(101)
+  }
+}
@@ -0,0 +1,36 @@
+//! Checks every day's fast solution against its brute-force reference, on
+//! many small generated inputs.
+
+use std::panic;
+
+use advent_of_code_2025::{DAYS, differential};
+
+const SEEDS: u64 = 50;
+
+const SIZES: [usize; 3] = [1, 2, 3];
+
+#[test]
+fn matches_references() {
+  // Panics are caught and reported as outcomes, and shrinking can cause a lot
+  // of them, so keep them from flooding the output.
+  let hook = panic::take_hook();
+  panic::set_hook(Box::new(|_| {}));
+
+  let mut mismatches = Vec::new();
+  for day in DAYS {
+    for seed in 0..SEEDS {
+      for size in SIZES {
+        if let Err(mismatch) = differential::check(day, seed, size) {
+          mismatches.push(mismatch);
+        }
+      }
+    }
+  }
+
+  panic::set_hook(hook);
+
+  for mismatch in &mismatches {
+    eprintln!("{mismatch}");
+  }
+  assert!(mismatches.is_empty(), "{} mismatches", mismatches.len());
+}
@@ -0,0 +1,34 @@
+//! Replays every input in the fuzz corpus, each of which once crashed a day.
+
+use std::{panic, time::Duration};
+
+use advent_of_code_2025::{
+  DAYS,
+  fuzz::{self, Corpus},
+};
+
+const LIMIT: Duration = Duration::from_secs(5);
+
+#[test]
+fn replays_corpus() {
+  let corpus = Corpus::new(Corpus::default_path());
+
+  let hook = panic::take_hook();
+  panic::set_hook(Box::new(|_| {}));
+
+  let mut failures = Vec::new();
+  for day in DAYS {
+    for (path, input, params) in corpus.cases(day).unwrap() {
+      if let Err(failure) = fuzz::run(day, &input, &params, LIMIT) {
+        failures.push(format!("{}: {failure}", path.display()));
+      }
+    }
+  }
+
+  panic::set_hook(hook);
+
+  for failure in &failures {
+    eprintln!("{failure}");
+  }
+  assert!(failures.is_empty(), "{} failures", failures.len());
+}